@@ -30,7 +30,7 @@ use glium::glutin::{Api, ControlFlow, ElementState, Event, GlProfile, GlRequest,
 use cgmath::{Matrix4, Point3, vec3};
 use isosurface::marching_cubes::MarchingCubes;
 use isosurface::linear_hashed_marching_cubes::LinearHashedMarchingCubes;
-use isosurface::source::CentralDifference;
+use isosurface::source::{CentralDifference, Source};
 use common::sources::{CubeSphere, Torus};
 use common::reinterpret_cast_slice;
 use common::text::layout_text;
@@ -57,9 +57,9 @@ where
     let mut indices = vec![];
 
     let (source, shape_name) = match shape % 2 {
-        0 => (CentralDifference::new(Box::new(Torus {})), "Torus"),
+        0 => (CentralDifference::new(Box::new(Torus {}) as Box<Source>), "Torus"),
         _ => (
-            CentralDifference::new(Box::new(CubeSphere {})),
+            CentralDifference::new(Box::new(CubeSphere {}) as Box<Source>),
             "Cube Sphere",
         ),
     };
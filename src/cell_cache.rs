@@ -0,0 +1,110 @@
+// Copyright 2018 Tristam MacDonald
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use sink::MeshSink;
+
+/// Tracks the vertex placed in each cell, so that dual methods (which emit one vertex per cell)
+/// can connect the cells surrounding each edge the surface crosses.
+pub struct CellCache {
+    cells_x: usize,
+    layers: [Vec<u32>; 2],
+}
+
+impl CellCache {
+    /// Create a new CellCache for a chunk with the given number of samples along x and y
+    pub fn new(size_x: usize, size_y: usize) -> CellCache {
+        let cells = (size_x - 1) * (size_y - 1);
+        CellCache {
+            cells_x: size_x - 1,
+            layers: [vec![0; cells], vec![0; cells]],
+        }
+    }
+
+    /// Record the index of the vertex placed in cell (x, y) of the current layer
+    pub fn put(&mut self, x: usize, y: usize, index: u32) {
+        self.layers[1][y * self.cells_x + x] = index;
+    }
+
+    /// Emit quads for each of the edges leaving the minimal corner of cell (x, y, z) that the
+    /// surface crosses, given the 8 corner values of that cell and the iso-level of the surface.
+    ///
+    /// Every other cell surrounding those edges has already been visited, so this must be called
+    /// after `put` for the current cell.
+    pub fn connect<M>(
+        &self,
+        x: usize,
+        y: usize,
+        z: usize,
+        values: &[f32; 8],
+        iso_level: f32,
+        sink: &mut M,
+    ) where
+        M: MeshSink,
+    {
+        let inside = values[0] <= iso_level;
+
+        if y > 0 && z > 0 && inside != (values[1] <= iso_level) {
+            let quad = [
+                self.get(0, x, y - 1),
+                self.get(0, x, y),
+                self.get(1, x, y),
+                self.get(1, x, y - 1),
+            ];
+            emit_quad(quad, inside, sink);
+        }
+        if x > 0 && z > 0 && inside != (values[3] <= iso_level) {
+            let quad = [
+                self.get(0, x - 1, y),
+                self.get(1, x - 1, y),
+                self.get(1, x, y),
+                self.get(0, x, y),
+            ];
+            emit_quad(quad, inside, sink);
+        }
+        if x > 0 && y > 0 && inside != (values[4] <= iso_level) {
+            let quad = [
+                self.get(1, x - 1, y - 1),
+                self.get(1, x, y - 1),
+                self.get(1, x, y),
+                self.get(1, x - 1, y),
+            ];
+            emit_quad(quad, inside, sink);
+        }
+    }
+
+    /// Update the cache when mesh extraction moves to the next layer
+    pub fn advance_layer(&mut self) {
+        self.layers.swap(0, 1);
+    }
+
+    #[inline]
+    fn get(&self, layer: usize, x: usize, y: usize) -> u32 {
+        self.layers[layer][y * self.cells_x + x]
+    }
+}
+
+/// Emit the two triangles of a quad, whose vertices are given in counter-clockwise order around
+/// the positive axis of the edge it straddles.
+fn emit_quad<M>(quad: [u32; 4], inside: bool, sink: &mut M)
+where
+    M: MeshSink,
+{
+    if inside {
+        sink.add_triangle(quad[0], quad[2], quad[1]);
+        sink.add_triangle(quad[0], quad[3], quad[2]);
+    } else {
+        sink.add_triangle(quad[0], quad[1], quad[2]);
+        sink.add_triangle(quad[0], quad[2], quad[3]);
+    }
+}
@@ -0,0 +1,240 @@
+// Copyright 2018 Tristam MacDonald
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use marching_cubes::MarchingCubes;
+use math::Vec3;
+use region::Region;
+use sink::{MeshSink, VecSink};
+use source::{HermiteSource, Source};
+use std::collections::hash_map;
+use std::collections::HashMap;
+
+/// Integer coordinates identifying a chunk of the world
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct ChunkCoord {
+    /// The index of the chunk along the x axis
+    pub x: i32,
+    /// The index of the chunk along the y axis
+    pub y: i32,
+    /// The index of the chunk along the z axis
+    pub z: i32,
+}
+
+impl ChunkCoord {
+    /// Create chunk coordinates
+    pub fn new(x: i32, y: i32, z: i32) -> Self {
+        Self { x, y, z }
+    }
+}
+
+/// The mesh extracted from a single chunk.
+///
+/// Vertices are in world coordinates, laid out as per
+/// [`MarchingCubes::extract`](../marching_cubes/struct.MarchingCubes.html#method.extract) or
+/// [`MarchingCubes::extract_with_normals`](../marching_cubes/struct.MarchingCubes.html#method.extract_with_normals).
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ChunkMesh {
+    /// The position of each vertex in world coordinates, followed by its normal if extracted with
+    /// normals
+    pub vertices: Vec<f32>,
+    /// The vertex indices of each triangle
+    pub indices: Vec<u32>,
+}
+
+/// Partitions an unbounded world into cubic chunks, and keeps track of the mesh extracted from
+/// each chunk.
+///
+/// Chunks are extracted independently of one another, in any order. Every sample position is
+/// derived from a single world-wide integer lattice, so neighbouring chunks sample their shared
+/// face at exactly the same points, and produce bit-identical vertices along it. The meshes of
+/// neighbouring chunks are therefore watertight, without any need to stitch them together.
+///
+/// Lattice coordinates are converted to `f32`, so the world must stay within roughly `2^24`
+/// samples of the origin along each axis for this guarantee to hold.
+pub struct ChunkedWorld {
+    size: usize,
+    step: f32,
+    iso_level: f32,
+    meshes: HashMap<ChunkCoord, ChunkMesh>,
+}
+
+impl ChunkedWorld {
+    /// Create a new ChunkedWorld, with chunks spanning `chunk_size` units along each axis.
+    ///
+    /// For a given `size`, each chunk will be evaluated as `size^3` voxels, with the last voxel of
+    /// each chunk coinciding with the first voxel of the next.
+    pub fn new(chunk_size: f32, size: usize) -> Self {
+        Self {
+            size,
+            step: chunk_size / (size - 1) as f32,
+            iso_level: 0.0,
+            meshes: HashMap::new(),
+        }
+    }
+
+    /// Set the iso-level at which to extract the surface of subsequently extracted chunks.
+    ///
+    /// Defaults to zero, which is the surface of a signed distance field. Samples at or below the
+    /// iso-level are considered to be inside the surface.
+    pub fn set_iso_level(&mut self, iso_level: f32) {
+        self.iso_level = iso_level;
+    }
+
+    /// The iso-level at which the surface is extracted
+    pub fn iso_level(&self) -> f32 {
+        self.iso_level
+    }
+
+    /// The coordinates of the chunk containing the given point
+    pub fn chunk_at(&self, point: Vec3) -> ChunkCoord {
+        let chunk_size = self.step * (self.size - 1) as f32;
+        ChunkCoord::new(
+            (point.x / chunk_size).floor() as i32,
+            (point.y / chunk_size).floor() as i32,
+            (point.z / chunk_size).floor() as i32,
+        )
+    }
+
+    /// The region of the world covered by the given chunk
+    pub fn region(&self, chunk: ChunkCoord) -> Region {
+        let cells = (self.size - 1) as f32;
+        let origin = self.lattice_origin(chunk) * self.step;
+        Region::new(origin, Vec3::one() * (cells * self.step), [self.size; 3])
+    }
+
+    /// Extracts the mesh for the given chunk from the given [`Source`](../source/trait.Source.html),
+    /// replacing any existing mesh for that chunk.
+    pub fn extract<S>(&mut self, source: &S, chunk: ChunkCoord) -> &ChunkMesh
+    where
+        S: Source,
+    {
+        let mut mesh = ChunkMesh::default();
+        {
+            let lattice = LatticeSource {
+                source,
+                step: self.step,
+            };
+            let mut sink = WorldSink {
+                sink: VecSink::new(&mut mesh.vertices, &mut mesh.indices),
+                step: self.step,
+            };
+            self.marching_cubes(chunk).extract_to(&lattice, &mut sink);
+        }
+        self.insert(chunk, mesh)
+    }
+
+    /// Extracts the mesh for the given chunk from the given [`HermiteSource`](../source/trait.HermiteSource.html),
+    /// replacing any existing mesh for that chunk.
+    pub fn extract_with_normals<S>(&mut self, source: &S, chunk: ChunkCoord) -> &ChunkMesh
+    where
+        S: HermiteSource,
+    {
+        let mut mesh = ChunkMesh::default();
+        {
+            let lattice = LatticeSource {
+                source,
+                step: self.step,
+            };
+            let mut sink = WorldSink {
+                sink: VecSink::new(&mut mesh.vertices, &mut mesh.indices),
+                step: self.step,
+            };
+            self.marching_cubes(chunk)
+                .extract_with_normals_to(&lattice, &mut sink);
+        }
+        self.insert(chunk, mesh)
+    }
+
+    /// The mesh most recently extracted for the given chunk, if any
+    pub fn mesh(&self, chunk: ChunkCoord) -> Option<&ChunkMesh> {
+        self.meshes.get(&chunk)
+    }
+
+    /// Forget the mesh for the given chunk, returning it if there was one
+    pub fn remove(&mut self, chunk: ChunkCoord) -> Option<ChunkMesh> {
+        self.meshes.remove(&chunk)
+    }
+
+    /// Iterate over all the chunks which have been extracted, and their meshes
+    pub fn meshes(&self) -> hash_map::Iter<'_, ChunkCoord, ChunkMesh> {
+        self.meshes.iter()
+    }
+
+    // Extraction happens in lattice space, where samples lie at integer coordinates, which are
+    // exactly representable and identical from one chunk to the next.
+    fn marching_cubes(&self, chunk: ChunkCoord) -> MarchingCubes {
+        let cells = (self.size - 1) as f32;
+        let mut marching_cubes = MarchingCubes::with_region(Region::new(
+            self.lattice_origin(chunk),
+            Vec3::one() * cells,
+            [self.size; 3],
+        ));
+        marching_cubes.set_iso_level(self.iso_level);
+        marching_cubes
+    }
+
+    fn lattice_origin(&self, chunk: ChunkCoord) -> Vec3 {
+        let cells = (self.size - 1) as i64;
+        Vec3::new(
+            (i64::from(chunk.x) * cells) as f32,
+            (i64::from(chunk.y) * cells) as f32,
+            (i64::from(chunk.z) * cells) as f32,
+        )
+    }
+
+    fn insert(&mut self, chunk: ChunkCoord, mesh: ChunkMesh) -> &ChunkMesh {
+        match self.meshes.entry(chunk) {
+            hash_map::Entry::Occupied(mut entry) => {
+                entry.insert(mesh);
+                entry.into_mut()
+            }
+            hash_map::Entry::Vacant(entry) => entry.insert(mesh),
+        }
+    }
+}
+
+/// Samples a source in lattice space, where each unit is one step between samples
+struct LatticeSource<'a, S: 'a> {
+    source: &'a S,
+    step: f32,
+}
+
+impl<'a, S: Source> Source for LatticeSource<'a, S> {
+    fn sample(&self, x: f32, y: f32, z: f32) -> f32 {
+        self.source.sample(x * self.step, y * self.step, z * self.step)
+    }
+}
+
+impl<'a, S: HermiteSource> HermiteSource for LatticeSource<'a, S> {
+    fn sample_normal(&self, x: f32, y: f32, z: f32) -> Vec3 {
+        self.source
+            .sample_normal(x * self.step, y * self.step, z * self.step)
+    }
+}
+
+/// Converts the positions of an extracted mesh from lattice space to world space
+struct WorldSink<'a> {
+    sink: VecSink<'a>,
+    step: f32,
+}
+
+impl<'a> MeshSink for WorldSink<'a> {
+    fn add_vertex(&mut self, position: Vec3, normal: Option<Vec3>) {
+        self.sink.add_vertex(position * self.step, normal);
+    }
+
+    fn add_triangle(&mut self, a: u32, b: u32, c: u32) {
+        self.sink.add_triangle(a, b, c);
+    }
+}
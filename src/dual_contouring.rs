@@ -0,0 +1,192 @@
+// Copyright 2018 Tristam MacDonald
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use source::HermiteSource;
+use cell_cache::CellCache;
+use marching_cubes_impl::{get_offset, interpolate};
+use marching_cubes_tables::{CORNERS, EDGE_CONNECTION};
+use math::Vec3;
+use region::Region;
+use sink::{MeshSink, VecSink};
+use qef::{clamp_to_cell, Qef};
+
+/// Extracts meshes from distance fields using the dual contouring algorithm.
+pub struct DualContouring {
+    region: Region,
+    iso_level: f32,
+    layers: [Vec<f32>; 2],
+}
+
+impl DualContouring {
+    /// Create a new DualContouring with the given chunk size.
+    ///
+    /// For a given `size`, this will evaluate chunks of `size^3` voxels, spanning the unit cube.
+    pub fn new(size: usize) -> DualContouring {
+        DualContouring::with_region(Region::unit(size))
+    }
+
+    /// Create a new DualContouring which samples the given [`Region`](../region/struct.Region.html).
+    pub fn with_region(region: Region) -> DualContouring {
+        let layer_size = region.samples[0] * region.samples[1];
+        DualContouring {
+            region,
+            iso_level: 0.0,
+            layers: [vec![0f32; layer_size], vec![0f32; layer_size]],
+        }
+    }
+
+    /// Set the iso-level at which to extract the surface.
+    ///
+    /// Defaults to zero, which is the surface of a signed distance field. Samples at or below the
+    /// iso-level are considered to be inside the surface.
+    pub fn set_iso_level(&mut self, iso_level: f32) {
+        self.iso_level = iso_level;
+    }
+
+    /// The iso-level at which the surface is extracted
+    pub fn iso_level(&self) -> f32 {
+        self.iso_level
+    }
+
+    /// Extracts a mesh from the given [`HermiteSource`](../source/trait.HermiteSource.html).
+    ///
+    /// The Source will be sampled across the region provided to the constructor, which defaults
+    /// to the range (0,0,0) to (1,1,1), and extracted vertices will lie within that region.
+    ///
+    /// Extracted vertices will be appended to `vertices` as triples of (x, y, z)
+    /// coordinates. Extracted triangles will be appended to `indices` as triples of
+    /// vertex indices.
+    pub fn extract<S>(&mut self, source: &S, vertices: &mut Vec<f32>, indices: &mut Vec<u32>)
+    where
+        S: HermiteSource,
+    {
+        self.extract_to(source, &mut VecSink::new(vertices, indices));
+    }
+
+    /// Extracts a mesh from the given [`HermiteSource`](../source/trait.HermiteSource.html).
+    ///
+    /// The Source will be sampled across the region provided to the constructor, which defaults
+    /// to the range (0,0,0) to (1,1,1), and extracted vertices will lie within that region.
+    ///
+    /// Extracted vertices will be appended to `vertices` as triples of (x, y, z)
+    /// coordinates, followed by the surface normals as triples of (x, y, z) dimensions. Extracted
+    /// triangles will be appended to `indices` as triples of vertex indices.
+    pub fn extract_with_normals<S>(
+        &mut self,
+        source: &S,
+        vertices: &mut Vec<f32>,
+        indices: &mut Vec<u32>,
+    ) where
+        S: HermiteSource,
+    {
+        self.extract_with_normals_to(source, &mut VecSink::new(vertices, indices));
+    }
+
+    /// Extracts a mesh from the given [`HermiteSource`](../source/trait.HermiteSource.html) into the given
+    /// [`MeshSink`](../sink/trait.MeshSink.html).
+    pub fn extract_to<S, M>(&mut self, source: &S, sink: &mut M)
+    where
+        S: HermiteSource,
+        M: MeshSink,
+    {
+        self.extract_impl(source, |_| None, sink);
+    }
+
+    /// Extracts a mesh with normals from the given [`HermiteSource`](../source/trait.HermiteSource.html)
+    /// into the given [`MeshSink`](../sink/trait.MeshSink.html).
+    pub fn extract_with_normals_to<S, M>(&mut self, source: &S, sink: &mut M)
+    where
+        S: HermiteSource,
+        M: MeshSink,
+    {
+        self.extract_impl(
+            source,
+            |v: Vec3| Some(source.sample_normal(v.x, v.y, v.z)),
+            sink,
+        );
+    }
+
+    fn extract_impl<S, N, M>(&mut self, source: &S, normal: N, sink: &mut M)
+    where
+        S: HermiteSource,
+        N: Fn(Vec3) -> Option<Vec3>,
+        M: MeshSink,
+    {
+        let [size_x, size_y, size_z] = self.region.samples;
+        let origin = self.region.origin;
+        let step = self.region.step();
+        let iso_level = self.iso_level;
+
+        // Cache layer zero of distance field values
+        source.sample_layer(&self.region, 0, &mut self.layers[0]);
+
+        let mut corners = [Vec3::zero(); 8];
+        let mut values = [0f32; 8];
+
+        let mut cell_cache = CellCache::new(size_x, size_y);
+        let mut index = 0u32;
+
+        for z in 0..size_z - 1 {
+            // Cache layer N+1 of isosurface values
+            source.sample_layer(&self.region, z + 1, &mut self.layers[1]);
+
+            // Place one vertex in each cell of the current layer that the surface passes through,
+            // and connect it to the vertices of the cells which share its minimal edges
+            for y in 0..size_y - 1 {
+                for x in 0..size_x - 1 {
+                    let mut cube_index = 0;
+                    for i in 0..8 {
+                        corners[i] = Vec3::new(
+                            origin.x + (x + CORNERS[i][0]) as f32 * step.x,
+                            origin.y + (y + CORNERS[i][1]) as f32 * step.y,
+                            origin.z + (z + CORNERS[i][2]) as f32 * step.z,
+                        );
+                        values[i] = self.layers[CORNERS[i][2]]
+                            [(y + CORNERS[i][1]) * size_x + x + CORNERS[i][0]];
+                        if values[i] <= iso_level {
+                            cube_index |= 1 << i;
+                        }
+                    }
+
+                    if cube_index == 0 || cube_index == 255 {
+                        continue;
+                    }
+
+                    let mut qef = Qef::new();
+                    for edge in &EDGE_CONNECTION {
+                        let (u, v) = (edge[0], edge[1]);
+                        if (values[u] <= iso_level) == (values[v] <= iso_level) {
+                            continue;
+                        }
+
+                        let offset = get_offset(values[u], values[v], iso_level);
+                        let p = interpolate(corners[u], corners[v], offset);
+                        qef.add(p, source.sample_normal(p.x, p.y, p.z));
+                    }
+
+                    let vertex = clamp_to_cell(qef.solve(), corners[0], corners[6]);
+
+                    cell_cache.put(x, y, index);
+                    sink.add_vertex(vertex, normal(vertex));
+                    index += 1;
+
+                    cell_cache.connect(x, y, z, &values, iso_level, sink);
+                }
+            }
+            cell_cache.advance_layer();
+
+            self.layers.swap(0, 1);
+        }
+    }
+}
@@ -0,0 +1,549 @@
+// Copyright 2018 Tristam MacDonald
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use math::Vec3;
+use std::io::{self, Write};
+
+/// The layout of the `vertices` produced by the extractors
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum VertexFormat {
+    /// Triples of (x, y, z) coordinates, as produced by `extract`
+    Positions,
+    /// Triples of (x, y, z) coordinates, each followed by a triple of (x, y, z) normal
+    /// dimensions, as produced by `extract_with_normals`
+    PositionsAndNormals,
+}
+
+impl VertexFormat {
+    /// The number of floats per vertex
+    pub fn stride(&self) -> usize {
+        match *self {
+            VertexFormat::Positions => 3,
+            VertexFormat::PositionsAndNormals => 6,
+        }
+    }
+
+    /// Whether each vertex includes a normal
+    pub fn has_normals(&self) -> bool {
+        *self == VertexFormat::PositionsAndNormals
+    }
+}
+
+/// Whether to write a file format as human-readable text, or as compact binary
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Encoding {
+    /// Human-readable text, which is larger but easy to inspect and diff
+    Ascii,
+    /// Little-endian binary, which is compact and faster to read
+    Binary,
+}
+
+/// Writes a mesh in the Wavefront OBJ format
+pub fn write_obj<W>(
+    writer: &mut W,
+    vertices: &[f32],
+    indices: &[u32],
+    format: VertexFormat,
+) -> io::Result<()>
+where
+    W: Write,
+{
+    let mesh = Mesh::new(vertices, indices, format)?;
+
+    for i in 0..mesh.vertex_count() {
+        let p = mesh.position(i);
+        writeln!(writer, "v {} {} {}", p.x, p.y, p.z)?;
+    }
+    if format.has_normals() {
+        for i in 0..mesh.vertex_count() {
+            let n = mesh.normal(i);
+            writeln!(writer, "vn {} {} {}", n.x, n.y, n.z)?;
+        }
+    }
+
+    // OBJ indices start from 1
+    for triangle in mesh.triangles() {
+        let [a, b, c] = [triangle[0] + 1, triangle[1] + 1, triangle[2] + 1];
+        if format.has_normals() {
+            writeln!(writer, "f {}//{} {}//{} {}//{}", a, a, b, b, c, c)?;
+        } else {
+            writeln!(writer, "f {} {} {}", a, b, c)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes a mesh in the Stanford PLY format
+pub fn write_ply<W>(
+    writer: &mut W,
+    vertices: &[f32],
+    indices: &[u32],
+    format: VertexFormat,
+    encoding: Encoding,
+) -> io::Result<()>
+where
+    W: Write,
+{
+    let mesh = Mesh::new(vertices, indices, format)?;
+
+    writeln!(writer, "ply")?;
+    match encoding {
+        Encoding::Ascii => writeln!(writer, "format ascii 1.0")?,
+        Encoding::Binary => writeln!(writer, "format binary_little_endian 1.0")?,
+    }
+    writeln!(writer, "element vertex {}", mesh.vertex_count())?;
+    for property in &["x", "y", "z", "nx", "ny", "nz"][..format.stride()] {
+        writeln!(writer, "property float {}", property)?;
+    }
+    writeln!(writer, "element face {}", indices.len() / 3)?;
+    writeln!(writer, "property list uchar uint vertex_indices")?;
+    writeln!(writer, "end_header")?;
+
+    for i in 0..mesh.vertex_count() {
+        let vertex = mesh.vertex(i);
+        match encoding {
+            Encoding::Ascii => {
+                let text: Vec<String> = vertex.iter().map(|f| f.to_string()).collect();
+                writeln!(writer, "{}", text.join(" "))?;
+            }
+            Encoding::Binary => {
+                for &f in vertex {
+                    write_f32(writer, f)?;
+                }
+            }
+        }
+    }
+
+    for triangle in mesh.triangles() {
+        match encoding {
+            Encoding::Ascii => {
+                writeln!(writer, "3 {} {} {}", triangle[0], triangle[1], triangle[2])?;
+            }
+            Encoding::Binary => {
+                writer.write_all(&[3])?;
+                for &index in &triangle {
+                    writer.write_all(&index.to_le_bytes())?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes a mesh in the STL format
+///
+/// STL has no notion of shared vertices, so every triangle is written out in full, along with
+/// its face normal. Any vertex normals are ignored.
+pub fn write_stl<W>(
+    writer: &mut W,
+    vertices: &[f32],
+    indices: &[u32],
+    format: VertexFormat,
+    encoding: Encoding,
+) -> io::Result<()>
+where
+    W: Write,
+{
+    let mesh = Mesh::new(vertices, indices, format)?;
+
+    match encoding {
+        Encoding::Ascii => writeln!(writer, "solid isosurface")?,
+        Encoding::Binary => {
+            let mut header = [0u8; 80];
+            let title = b"isosurface";
+            header[..title.len()].copy_from_slice(title);
+            writer.write_all(&header)?;
+            writer.write_all(&((indices.len() / 3) as u32).to_le_bytes())?;
+        }
+    }
+
+    for triangle in mesh.triangles() {
+        let corners = [
+            mesh.position(triangle[0] as usize),
+            mesh.position(triangle[1] as usize),
+            mesh.position(triangle[2] as usize),
+        ];
+        let normal = (corners[1] - corners[0])
+            .cross(corners[2] - corners[0])
+            .normalize();
+
+        match encoding {
+            Encoding::Ascii => {
+                writeln!(
+                    writer,
+                    "facet normal {} {} {}",
+                    normal.x, normal.y, normal.z
+                )?;
+                writeln!(writer, "  outer loop")?;
+                for p in &corners {
+                    writeln!(writer, "    vertex {} {} {}", p.x, p.y, p.z)?;
+                }
+                writeln!(writer, "  endloop")?;
+                writeln!(writer, "endfacet")?;
+            }
+            Encoding::Binary => {
+                for v in [normal].iter().chain(corners.iter()) {
+                    write_f32(writer, v.x)?;
+                    write_f32(writer, v.y)?;
+                    write_f32(writer, v.z)?;
+                }
+                // Attribute byte count, which is unused
+                writer.write_all(&[0, 0])?;
+            }
+        }
+    }
+
+    if encoding == Encoding::Ascii {
+        writeln!(writer, "endsolid isosurface")?;
+    }
+
+    Ok(())
+}
+
+/// A glTF metallic-roughness material
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Material {
+    /// The base colour of the material, as linear RGBA, with each component from 0 to 1
+    pub base_color: [f32; 4],
+    /// How metallic the material is, from 0 to 1
+    pub metallic: f32,
+    /// How rough the material is, from 0 to 1
+    pub roughness: f32,
+}
+
+impl Default for Material {
+    fn default() -> Self {
+        Material {
+            base_color: [1.0; 4],
+            metallic: 0.0,
+            roughness: 1.0,
+        }
+    }
+}
+
+/// Optional extras to include when writing a glTF file
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct GltfOptions<'a> {
+    /// Per-vertex colours, as linear RGBA, with 4 floats for each vertex. Written as the `COLOR_0`
+    /// attribute.
+    pub colors: Option<&'a [f32]>,
+    /// The material to apply to the mesh
+    pub material: Option<Material>,
+}
+
+/// Writes a mesh as a single binary glTF 2.0 (`.glb`) file
+///
+/// The file contains a single scene, with a single node referencing a single mesh. Normals are
+/// normalised as glTF requires, and indices are stored as 16-bit integers whenever there are few
+/// enough vertices, and as 32-bit integers otherwise.
+///
+/// Returns an `InvalidInput` error, without writing anything, if any vertex position isn't
+/// finite, or any material factor lies outside 0 to 1.
+pub fn write_glb<W>(
+    writer: &mut W,
+    vertices: &[f32],
+    indices: &[u32],
+    format: VertexFormat,
+    options: &GltfOptions,
+) -> io::Result<()>
+where
+    W: Write,
+{
+    let mesh = Mesh::new(vertices, indices, format)?;
+    let vertex_count = mesh.vertex_count();
+    if indices.is_empty() {
+        return Err(invalid_input(
+            "glTF meshes must contain at least one triangle",
+        ));
+    }
+    if let Some(colors) = options.colors {
+        if colors.len() != vertex_count * 4 {
+            return Err(invalid_input(
+                "there must be one RGBA colour for each vertex",
+            ));
+        }
+    }
+    if let Some(material) = options.material {
+        let [r, g, b, a] = material.base_color;
+        let factors = [r, g, b, a, material.metallic, material.roughness];
+        if !factors.iter().all(|factor| (0.0..=1.0).contains(factor)) {
+            return Err(invalid_input("material factors must lie between 0 and 1"));
+        }
+    }
+
+    let mut gltf = GltfBuilder::new();
+    let mut attributes = vec![];
+
+    // Positions, along with the bounds glTF requires for them
+    let mut min = mesh.position(0);
+    let mut max = min;
+    let mut data = Vec::with_capacity(vertex_count * 12);
+    for i in 0..vertex_count {
+        let p = mesh.position(i);
+        for axis in 0..3 {
+            if !p[axis].is_finite() {
+                return Err(invalid_input("vertex positions must be finite"));
+            }
+            min[axis] = min[axis].min(p[axis]);
+            max[axis] = max[axis].max(p[axis]);
+            data.extend_from_slice(&p[axis].to_bits().to_le_bytes());
+        }
+    }
+    // Written at double precision, so that the bounds match the data exactly once parsed
+    let bounds = format!(
+        r#","min":[{},{},{}],"max":[{},{},{}]"#,
+        f64::from(min.x),
+        f64::from(min.y),
+        f64::from(min.z),
+        f64::from(max.x),
+        f64::from(max.y),
+        f64::from(max.z)
+    );
+    let accessor = gltf.accessor(&data, ARRAY_BUFFER, FLOAT, vertex_count, "VEC3", &bounds);
+    attributes.push(format!(r#""POSITION":{}"#, accessor));
+
+    if format.has_normals() {
+        let mut data = Vec::with_capacity(vertex_count * 12);
+        for i in 0..vertex_count {
+            let n = mesh.normal(i).normalize();
+            for axis in 0..3 {
+                data.extend_from_slice(&n[axis].to_bits().to_le_bytes());
+            }
+        }
+        let accessor = gltf.accessor(&data, ARRAY_BUFFER, FLOAT, vertex_count, "VEC3", "");
+        attributes.push(format!(r#""NORMAL":{}"#, accessor));
+    }
+
+    if let Some(colors) = options.colors {
+        let mut data = Vec::with_capacity(vertex_count * 16);
+        for &c in colors {
+            data.extend_from_slice(&c.to_bits().to_le_bytes());
+        }
+        let accessor = gltf.accessor(&data, ARRAY_BUFFER, FLOAT, vertex_count, "VEC4", "");
+        attributes.push(format!(r#""COLOR_0":{}"#, accessor));
+    }
+
+    // The largest value of each index type is reserved for primitive restart
+    let mut data = vec![];
+    let component_type = if vertex_count <= u16::MAX as usize {
+        for triangle in mesh.triangles() {
+            for &index in &triangle {
+                data.extend_from_slice(&(index as u16).to_le_bytes());
+            }
+        }
+        UNSIGNED_SHORT
+    } else {
+        for triangle in mesh.triangles() {
+            for &index in &triangle {
+                data.extend_from_slice(&index.to_le_bytes());
+            }
+        }
+        UNSIGNED_INT
+    };
+    let index_accessor = gltf.accessor(
+        &data,
+        ELEMENT_ARRAY_BUFFER,
+        component_type,
+        indices.len(),
+        "SCALAR",
+        "",
+    );
+
+    let mut primitive = format!(
+        r#"{{"attributes":{{{}}},"indices":{},"mode":4"#,
+        attributes.join(","),
+        index_accessor
+    );
+    let mut materials = String::new();
+    if let Some(material) = options.material {
+        let c = material.base_color;
+        primitive.push_str(r#","material":0"#);
+        materials = format!(
+            r#","materials":[{{"pbrMetallicRoughness":{{"baseColorFactor":[{},{},{},{}],"metallicFactor":{},"roughnessFactor":{}}}}}]"#,
+            c[0], c[1], c[2], c[3], material.metallic, material.roughness
+        );
+    }
+    primitive.push('}');
+
+    let json = format!(
+        concat!(
+            r#"{{"asset":{{"version":"2.0","generator":"isosurface"}},"#,
+            r#""scene":0,"scenes":[{{"nodes":[0]}}],"nodes":[{{"mesh":0}}],"#,
+            r#""meshes":[{{"primitives":[{}]}}]{},"#,
+            r#""buffers":[{{"byteLength":{}}}],"bufferViews":[{}],"accessors":[{}]}}"#
+        ),
+        primitive,
+        materials,
+        gltf.buffer.len(),
+        gltf.buffer_views.join(","),
+        gltf.accessors.join(",")
+    );
+
+    // Both chunks must be padded to a multiple of 4 bytes, with spaces and zeroes respectively
+    let mut json = json.into_bytes();
+    while json.len() % 4 != 0 {
+        json.push(b' ');
+    }
+    let buffer = gltf.buffer;
+
+    let length = 12 + 8 + json.len() + 8 + buffer.len();
+    writer.write_all(b"glTF")?;
+    writer.write_all(&2u32.to_le_bytes())?;
+    writer.write_all(&(length as u32).to_le_bytes())?;
+    writer.write_all(&(json.len() as u32).to_le_bytes())?;
+    writer.write_all(b"JSON")?;
+    writer.write_all(&json)?;
+    writer.write_all(&(buffer.len() as u32).to_le_bytes())?;
+    writer.write_all(b"BIN\0")?;
+    writer.write_all(&buffer)?;
+
+    Ok(())
+}
+
+// glTF buffer view targets
+const ARRAY_BUFFER: u32 = 34962;
+const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+
+// glTF accessor component types
+const UNSIGNED_SHORT: u32 = 5123;
+const UNSIGNED_INT: u32 = 5125;
+const FLOAT: u32 = 5126;
+
+/// Accumulates the binary buffer of a glTF file, and the buffer views and accessors into it
+struct GltfBuilder {
+    buffer: Vec<u8>,
+    buffer_views: Vec<String>,
+    accessors: Vec<String>,
+}
+
+impl GltfBuilder {
+    fn new() -> Self {
+        GltfBuilder {
+            buffer: vec![],
+            buffer_views: vec![],
+            accessors: vec![],
+        }
+    }
+
+    /// Append data to the buffer, with a buffer view and an accessor covering it, and return the
+    /// index of the accessor
+    fn accessor(
+        &mut self,
+        data: &[u8],
+        target: u32,
+        component_type: u32,
+        count: usize,
+        kind: &str,
+        extra: &str,
+    ) -> usize {
+        let offset = self.buffer.len();
+        self.buffer.extend_from_slice(data);
+        while !self.buffer.len().is_multiple_of(4) {
+            self.buffer.push(0);
+        }
+
+        self.buffer_views.push(format!(
+            r#"{{"buffer":0,"byteOffset":{},"byteLength":{},"target":{}}}"#,
+            offset,
+            data.len(),
+            target
+        ));
+        self.accessors.push(format!(
+            r#"{{"bufferView":{},"componentType":{},"count":{},"type":"{}"{}}}"#,
+            self.buffer_views.len() - 1,
+            component_type,
+            count,
+            kind,
+            extra
+        ));
+        self.accessors.len() - 1
+    }
+}
+
+/// A validated view of the output of an extractor
+struct Mesh<'a> {
+    vertices: &'a [f32],
+    indices: &'a [u32],
+    stride: usize,
+}
+
+impl<'a> Mesh<'a> {
+    /// Check that the vertices match the format, and that every index refers to a vertex
+    pub fn new(vertices: &'a [f32], indices: &'a [u32], format: VertexFormat) -> io::Result<Self> {
+        let stride = format.stride();
+        if !vertices.len().is_multiple_of(stride) {
+            return Err(invalid_input(
+                "vertex data is not a whole number of vertices",
+            ));
+        }
+        if !indices.len().is_multiple_of(3) {
+            return Err(invalid_input(
+                "index data is not a whole number of triangles",
+            ));
+        }
+        let vertex_count = vertices.len() / stride;
+        if indices.iter().any(|&i| i as usize >= vertex_count) {
+            return Err(invalid_input(
+                "index refers to a vertex that does not exist",
+            ));
+        }
+
+        Ok(Mesh {
+            vertices,
+            indices,
+            stride,
+        })
+    }
+
+    /// The number of vertices in the mesh
+    pub fn vertex_count(&self) -> usize {
+        self.vertices.len() / self.stride
+    }
+
+    /// All the floats making up a vertex
+    pub fn vertex(&self, i: usize) -> &'a [f32] {
+        &self.vertices[i * self.stride..(i + 1) * self.stride]
+    }
+
+    /// The position of a vertex
+    pub fn position(&self, i: usize) -> Vec3 {
+        let v = self.vertex(i);
+        Vec3::new(v[0], v[1], v[2])
+    }
+
+    /// The normal of a vertex. Only valid if the format includes normals.
+    pub fn normal(&self, i: usize) -> Vec3 {
+        let v = self.vertex(i);
+        Vec3::new(v[3], v[4], v[5])
+    }
+
+    /// The vertex indices of each triangle, wound counter-clockwise when viewed from outside the
+    /// surface.
+    ///
+    /// The extractors wind triangles clockwise when viewed from outside the surface, whereas
+    /// these file formats expect the opposite, so every triangle is reversed.
+    pub fn triangles(&self) -> impl Iterator<Item = [u32; 3]> + 'a {
+        self.indices.chunks(3).map(|t| [t[0], t[2], t[1]])
+    }
+}
+
+fn write_f32<W: Write>(writer: &mut W, f: f32) -> io::Result<()> {
+    writer.write_all(&f.to_bits().to_le_bytes())
+}
+
+fn invalid_input(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
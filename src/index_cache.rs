@@ -12,90 +12,79 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+/// Marks an edge whose vertex has not been generated yet
+pub const EMPTY: u32 = u32::MAX;
+
 /// Tracks vertex indices to avoid emitting duplicate vertices during marching cubes mesh generation
+///
+/// Every edge of the sampling grid is cached in exactly one place, so each vertex is emitted once,
+/// no matter which of the (up to 4) cells sharing its edge reaches it first.
 pub struct IndexCache {
-    size: usize,
-    layers: [Vec<[u32; 4]>; 2],
-    rows: [Vec<[u32; 3]>; 2],
-    cells: [[u32; 2]; 2],
-    current_cell: [u32; 12],
+    size_x: usize,
+    // The x and y aligned edges of the planes at the bottom and top of the current layer
+    planes: [Vec<[u32; 2]>; 2],
+    // The z aligned edges crossing the current layer
+    verticals: Vec<u32>,
 }
 
 impl IndexCache {
-    /// Create a new IndexCache for the given chunk size
-    pub fn new(size: usize) -> IndexCache {
+    /// Create a new IndexCache for a chunk with the given number of samples along x and y
+    pub fn new(size_x: usize, size_y: usize) -> IndexCache {
         IndexCache {
-            size,
-            layers: [vec![[0; 4]; size * size], vec![[0; 4]; size * size]],
-            rows: [vec![[0; 3]; size], vec![[0; 3]; size]],
-            cells: [[0; 2]; 2],
-            current_cell: [0; 12],
+            size_x,
+            planes: [
+                vec![[EMPTY; 2]; size_x * size_y],
+                vec![[EMPTY; 2]; size_x * size_y],
+            ],
+            verticals: vec![EMPTY; size_x * size_y],
         }
     }
 
     /// Put an index in the cache at the given (x, y, edge) coordinate
     pub fn put(&mut self, x: usize, y: usize, edge: usize, index: u32) {
-        if let 4...7 = edge {
-            self.layers[1][y * self.size + x][edge - 4] = index;
-        }
-
-        match edge {
-            6 => self.rows[1][x][0] = index,
-            11 => self.rows[1][x][1] = index,
-            10 => self.rows[1][x][2] = index,
-            _ => (),
-        }
-
-        match edge {
-            5 | 10 => self.cells[1][0] = index,
-            _ => (),
-        }
-
-        self.current_cell[edge] = index;
+        *self.slot(x, y, edge) = index;
     }
 
-    /// Retrieve an index from the cache at the given (x, y, edge) coordinate
+    /// Retrieve an index from the cache at the given (x, y, edge) coordinate, or `EMPTY`
     pub fn get(&mut self, x: usize, y: usize, edge: usize) -> u32 {
-        let result = match edge {
-            0...3 => self.layers[0][y * self.size + x][edge],
-            4 => self.rows[0][x][0],
-            8 => self.rows[0][x][1],
-            9 => self.rows[0][x][2],
-            7 | 11 => self.cells[0][1],
-            _ => 0,
-        };
-
-        if result > 0 {
-            result
-        } else {
-            self.current_cell[edge]
-        }
+        *self.slot(x, y, edge)
     }
 
-    /// Update the cache when mesh extraction moves to the next cell
-    pub fn advance_cell(&mut self) {
-        self.cells.swap(0, 1);
-        for i in &mut self.current_cell {
-            *i = 0;
-        }
+    /// The indices of the x and y aligned edges on the bottom plane of the current layer
+    #[cfg(feature = "rayon")]
+    pub fn bottom_plane(&self) -> &[[u32; 2]] {
+        &self.planes[0]
     }
 
-    /// Update the cache when mesh extraction moves to the next row
-    pub fn advance_row(&mut self) {
-        self.rows.swap(0, 1);
-        for i in &mut self.cells[0] {
-            *i = 0;
-        }
+    /// Mutable access to the x and y aligned edges on the bottom plane of the current layer
+    #[cfg(feature = "rayon")]
+    pub fn bottom_plane_mut(&mut self) -> &mut [[u32; 2]] {
+        &mut self.planes[0]
     }
 
     /// Update the cache when mesh extraction moves to the next layer
     pub fn advance_layer(&mut self) {
-        self.layers.swap(0, 1);
-        for i in &mut self.cells[0] {
-            *i = 0;
+        self.planes.swap(0, 1);
+        for i in &mut self.planes[1] {
+            *i = [EMPTY; 2];
         }
-        for i in &mut self.rows[0] {
-            *i = [0; 3];
+        for i in &mut self.verticals {
+            *i = EMPTY;
+        }
+    }
+
+    fn slot(&mut self, x: usize, y: usize, edge: usize) -> &mut u32 {
+        let size_x = self.size_x;
+        let plane = edge / 4;
+        match edge {
+            0 | 4 => &mut self.planes[plane][y * size_x + x][0],
+            1 | 5 => &mut self.planes[plane][y * size_x + x + 1][1],
+            2 | 6 => &mut self.planes[plane][(y + 1) * size_x + x][0],
+            3 | 7 => &mut self.planes[plane][y * size_x + x][1],
+            8 => &mut self.verticals[y * size_x + x],
+            9 => &mut self.verticals[y * size_x + x + 1],
+            10 => &mut self.verticals[(y + 1) * size_x + x + 1],
+            _ => &mut self.verticals[(y + 1) * size_x + x],
         }
     }
 }
@@ -14,26 +14,73 @@
 
 //! Algorithms for extracting meshe data from isosurfaces.
 
+#[cfg(feature = "rayon")]
+extern crate rayon;
+
 /// Common math types
 pub mod math;
 
 /// Traits for defining isosurface data sources
 pub mod source;
 
+/// Signed distance functions for common primitives, and CSG operations to combine them.
+///
+/// Primitives are centred on the origin. Most of the distance functions follow Inigo Quilez's
+/// [catalogue of distance functions](https://iquilezles.org/articles/distfunctions/). Each is a
+/// [Field](source/trait.Field.html), so normals are exact.
+pub mod sdf;
+
+/// Adaptors to move, rotate, scale, mirror and repeat sources.
+///
+/// Primitives from the [sdf](sdf/index.html) module are centred on the origin, so these are
+/// typically needed to place them inside the region being extracted.
+pub mod transform;
+
+/// Procedural noise sources, deterministic from a seed, for generating terrain
+pub mod noise;
+
+/// A source backed by a dense grid of voxels, for meshing scanned or simulated volumes
+pub mod voxel_grid;
+
+/// Traits for receiving extracted meshes
+pub mod sink;
+
+/// Describes the region of space sampled by the extractors
+pub mod region;
+
 /// Convert isosurfaces to meshes using marching cubes.
 ///
 /// Pros:
 ///
 /// * Pretty fast.
 /// * Extraction has no dependencies on neighbouring chunks.
+/// * Optional transition cells, laid out as in [Transvoxel](http://transvoxel.org/), to stitch
+///   chunks of differing levels of detail.
 ///
 /// Cons:
 ///
 /// * Produces a lot of small triangle slivers
 /// * Can't accurately reproduce sharp corners in the isosurface.
-/// * Cracks between chunks of differing levels of detail
 pub mod marching_cubes;
 
+/// Convert isosurfaces to meshes using marching tetrahedra.
+///
+/// Pros:
+///
+/// * Every cell is split into tetrahedra, which have no ambiguous cases, so meshes are always
+///   watertight.
+/// * Needs only a 16 entry triangulation table.
+///
+/// Cons:
+///
+/// * Produces two to three times as many triangles as marching cubes, with even more slivers.
+/// * The mesh follows the diagonals used to split each cell, which can show up as faint
+///   grid-aligned artifacts.
+pub mod marching_tetrahedra;
+
+/// Manage an unbounded world of marching cubes chunks, whose meshes join seamlessly.
+pub mod chunks;
+
 /// Convert isosurfaces to point clouds
 ///
 /// Pros:
@@ -53,15 +100,67 @@ pub mod point_cloud;
 ///
 /// * Roughly twice as fast as standard marching cubes.
 /// * Accurately reproduce sharp grid-aligned corners in the underlying isosurface.
+/// * Meshes are closed, even where octree leaves of very different sizes meet, and manifold once
+///   the octree is balanced.
+/// * Where resolution is spent can be customised with a
+///   [RefinementPolicy](../refinement/trait.RefinementPolicy.html).
 ///
 /// Cons:
 ///
-/// * Still can't accurately reproduce sharp corners which are not grid-aligned.
+/// * Can't accurately reproduce sharp corners which are not grid-aligned, unless extracted with
+///   Dual Marching Cubes, which requires a [HermiteSource](../source/trait.HermiteSource.html).
 /// * Still no level-of-detail for neighbouring chunks.
 pub mod linear_hashed_marching_cubes;
 
+/// Policies deciding where the octree of
+/// [LinearHashedMarchingCubes](linear_hashed_marching_cubes/struct.LinearHashedMarchingCubes.html)
+/// is refined
+pub mod refinement;
+
+/// Interleaved integer coordinates which identify the nodes of an octree
+pub mod morton;
+
+/// A sparse octree stored in a hash table, as used by
+/// [LinearHashedMarchingCubes](linear_hashed_marching_cubes/struct.LinearHashedMarchingCubes.html)
+pub mod linear_hashed_octree;
+
+/// Convert isosurfaces to meshes using dual contouring.
+///
+/// This is an implementation of the paper [Dual Contouring of Hermite Data](https://www.cs.rice.edu/~jwarren/papers/dualcontour.pdf).
+///
+/// Pros:
+///
+/// * Accurately reproduces sharp corners, even when they are not grid-aligned.
+/// * Produces fewer, more regular triangles than marching cubes.
+///
+/// Cons:
+///
+/// * Requires a [HermiteSource](../source/trait.HermiteSource.html), and samples its normals at every edge crossing.
+/// * Meshes may be non-manifold where several sheets of the surface pass through a single cell.
+pub mod dual_contouring;
+
+/// Convert isosurfaces to meshes using naive surface nets.
+///
+/// Pros:
+///
+/// * As fast as marching cubes, with meshes of about the same size.
+/// * Produces far fewer triangle slivers than marching cubes.
+///
+/// Cons:
+///
+/// * Smooths away sharp corners in the isosurface.
+/// * Meshes may be non-manifold where several sheets of the surface pass through a single cell.
+pub mod surface_nets;
+
+/// Write extracted meshes to common file formats
+pub mod export;
+
+mod cell_cache;
 mod index_cache;
-mod linear_hashed_octree;
 mod marching_cubes_impl;
 mod marching_cubes_tables;
-mod morton;
+mod marching_tetrahedra_tables;
+mod qef;
+mod tetrahedron_index_cache;
+mod transvoxel_impl;
+mod transvoxel_tables;
@@ -14,18 +14,19 @@
 
 use linear_hashed_octree::LinearHashedOctree;
 use morton::Morton;
-use marching_cubes_impl::{get_offset, interpolate, march_cube};
-use marching_cubes_tables::EDGE_CONNECTION;
+use marching_cubes_impl::{get_offset, interpolate, march_cube, Triangle};
+use marching_cubes_tables::{CORNERS, EDGE_CONNECTION};
 use math::Vec3;
+use qef::{clamp_to_cell, Qef};
+use refinement::{Distance, OctreeNode, RefinementPolicy};
+use region::Region;
+use sink::{MeshSink, VecSink};
 use source::{HermiteSource, Source};
 use std::collections::HashMap;
 
 // Morton cube corners are ordered differently to the marching cubes tables, so remap them to match.
 const REMAP_CUBE: [usize; 8] = [2, 3, 1, 0, 6, 7, 5, 4];
 
-// Used to compute the diagonal dimension (i.e. 3-dimensional hypotenuse) of a cube.
-const SQRT_OF_3: f32 = 1.732_050_807_57;
-
 // Uniquely identifies an edge by its terminal vertices
 #[derive(Debug, Hash, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
 struct Edge(Morton, Morton);
@@ -41,8 +42,15 @@ impl Edge {
 }
 
 /// Extracts meshes from distance fields using marching cubes over a linear hashed octree.
-pub struct LinearHashedMarchingCubes {
+///
+/// Where the octree is refined is decided by a [`RefinementPolicy`](../refinement/trait.RefinementPolicy.html),
+/// which defaults to refining uniformly along the whole surface.
+pub struct LinearHashedMarchingCubes<R = Distance> {
     max_depth: usize,
+    region: Region,
+    iso_level: f32,
+    balanced: bool,
+    refinement: R,
 }
 
 impl LinearHashedMarchingCubes {
@@ -51,13 +59,97 @@ impl LinearHashedMarchingCubes {
     /// The depth of the internal octree will be at most `max_depth`, causing the tree to span the
     /// equivalent of a cubic grid at most `2.pow(max_depth)` in either direction.
     pub fn new(max_depth: usize) -> Self {
-        Self { max_depth }
+        Self::new_with_refinement(max_depth, Distance)
+    }
+
+    /// Create a new LinearHashedMarchingCubes which samples the given [`Region`](../region/struct.Region.html).
+    ///
+    /// The octree is stretched to fit the region, and its maximum depth is chosen so that the
+    /// smallest cells are no larger than the spacing between samples along any axis.
+    pub fn with_region(region: Region) -> Self {
+        Self::with_region_and_refinement(region, Distance)
+    }
+}
+
+impl<R> LinearHashedMarchingCubes<R> {
+    /// Create a new LinearHashedMarchingCubes, which refines its octree according to the given
+    /// [`RefinementPolicy`](../refinement/trait.RefinementPolicy.html).
+    ///
+    /// The maximum depth is as per [`new`](#method.new).
+    pub fn new_with_refinement(max_depth: usize, refinement: R) -> Self {
+        Self {
+            max_depth,
+            region: Region::unit((1 << max_depth) + 1),
+            iso_level: 0.0,
+            balanced: false,
+            refinement,
+        }
+    }
+
+    /// Create a new LinearHashedMarchingCubes which samples the given [`Region`](../region/struct.Region.html),
+    /// and refines its octree according to the given [`RefinementPolicy`](../refinement/trait.RefinementPolicy.html).
+    ///
+    /// The maximum depth is as per [`with_region`](#method.with_region).
+    pub fn with_region_and_refinement(region: Region, refinement: R) -> Self {
+        let cells = region.samples.iter().max().cloned().unwrap_or(2) - 1;
+        let mut max_depth = 0;
+        while (1 << max_depth) < cells {
+            max_depth += 1;
+        }
+
+        Self {
+            max_depth,
+            region,
+            iso_level: 0.0,
+            balanced: false,
+            refinement,
+        }
+    }
+
+    /// Set the iso-level at which to extract the surface.
+    ///
+    /// Defaults to zero, which is the surface of a signed distance field. Samples at or below the
+    /// iso-level are considered to be inside the surface.
+    pub fn set_iso_level(&mut self, iso_level: f32) {
+        self.iso_level = iso_level;
+    }
+
+    /// The iso-level at which the surface is extracted
+    pub fn iso_level(&self) -> f32 {
+        self.iso_level
+    }
+
+    /// Set whether the octree is balanced before extraction.
+    ///
+    /// Octree leaves are sized to follow the source, so neighbouring leaves can differ by many
+    /// levels, which leaves long slivers in the mesh where they meet. A balanced octree splits
+    /// leaves until none is more than one level coarser than any leaf it touches. The mesh is
+    /// closed either way, but only a balanced octree guarantees a manifold mesh: where leaves of
+    /// very different sizes meet, an edge may be shared by four triangles. Defaults to false.
+    pub fn set_balanced(&mut self, balanced: bool) {
+        self.balanced = balanced;
+    }
+
+    /// Whether the octree is balanced before extraction
+    pub fn balanced(&self) -> bool {
+        self.balanced
+    }
+
+    /// The policy deciding where the octree is refined
+    pub fn refinement(&self) -> &R {
+        &self.refinement
+    }
+
+    /// The policy deciding where the octree is refined, such as to move the camera of a
+    /// [`ScreenSpaceError`](../refinement/struct.ScreenSpaceError.html) policy between extractions
+    pub fn refinement_mut(&mut self) -> &mut R {
+        &mut self.refinement
     }
 
     /// Extracts a mesh from the given [`Source`](../source/trait.Source.html).
     ///
-    /// The Source will be sampled in the range (0,0,0) to (1,1,1), with the number of steps
-    /// determined by the size provided to the constructor.
+    /// The Source will be sampled across the region provided to the constructor, which defaults
+    /// to the range (0,0,0) to (1,1,1), and extracted vertices will lie within that region.
     ///
     /// Extracted vertices will be appended to `vertices` as triples of (x, y, z)
     /// coordinates. Extracted triangles will be appended to `indices` as triples of
@@ -65,22 +157,15 @@ impl LinearHashedMarchingCubes {
     pub fn extract<S>(&mut self, source: &S, vertices: &mut Vec<f32>, indices: &mut Vec<u32>)
     where
         S: Source,
+        R: RefinementPolicy<S>,
     {
-        self.extract_impl(
-            source,
-            |v: Vec3| {
-                vertices.push(v.x);
-                vertices.push(v.y);
-                vertices.push(v.z);
-            },
-            indices,
-        );
+        self.extract_to(source, &mut VecSink::new(vertices, indices));
     }
 
     /// Extracts a mesh from the given [`HermiteSource`](../source/trait.HermiteSource.html).
     ///
-    /// The Source will be sampled in the range (0,0,0) to (1,1,1), with the number of steps
-    /// determined by the size provided to the constructor.
+    /// The Source will be sampled across the region provided to the constructor, which defaults
+    /// to the range (0,0,0) to (1,1,1), and extracted vertices will lie within that region.
     ///
     /// Extracted vertices will be appended to `vertices` as triples of (x, y, z)
     /// coordinates, followed by the surface normals as triples of (x, y, z) dimensions. Extracted
@@ -92,51 +177,177 @@ impl LinearHashedMarchingCubes {
         indices: &mut Vec<u32>,
     ) where
         S: HermiteSource,
+        R: RefinementPolicy<S>,
+    {
+        self.extract_with_normals_to(source, &mut VecSink::new(vertices, indices));
+    }
+
+    /// Extracts a mesh from the given [`Source`](../source/trait.Source.html) into the given
+    /// [`MeshSink`](../sink/trait.MeshSink.html).
+    pub fn extract_to<S, M>(&mut self, source: &S, sink: &mut M)
+    where
+        S: Source,
+        M: MeshSink,
+        R: RefinementPolicy<S>,
+    {
+        let region = self.region;
+        self.extract_impl(
+            source,
+            |key: Morton, distance: f32| (leaf_centre(&region, key), distance),
+            |_| None,
+            sink,
+        );
+    }
+
+    /// Extracts a mesh with normals from the given [`HermiteSource`](../source/trait.HermiteSource.html)
+    /// into the given [`MeshSink`](../sink/trait.MeshSink.html).
+    pub fn extract_with_normals_to<S, M>(&mut self, source: &S, sink: &mut M)
+    where
+        S: HermiteSource,
+        M: MeshSink,
+        R: RefinementPolicy<S>,
     {
+        let region = self.region;
         self.extract_impl(
             source,
-            |v: Vec3| {
-                let n = source.sample_normal(v.x, v.y, v.z);
-                vertices.push(v.x);
-                vertices.push(v.y);
-                vertices.push(v.z);
-                vertices.push(n.x);
-                vertices.push(n.y);
-                vertices.push(n.z);
+            |key: Morton, distance: f32| (leaf_centre(&region, key), distance),
+            |v: Vec3| Some(source.sample_normal(v.x, v.y, v.z)),
+            sink,
+        );
+    }
+
+    /// Extracts a mesh from the given [`HermiteSource`](../source/trait.HermiteSource.html), using
+    /// Dual Marching Cubes.
+    ///
+    /// This follows the paper [Dual Marching Cubes: Primal Contouring of Dual Grids](https://www.cs.rice.edu/~jwarren/papers/dmc.pdf).
+    /// Rather than sitting at the centre of each octree leaf, the vertices of the dual grid are
+    /// placed at the point which best fits the tangent planes of the surface within that leaf,
+    /// as in dual contouring. Cells of the dual grid then straddle sharp corners and edges of the
+    /// surface, even where those don't line up with the octree.
+    ///
+    /// Vertices and triangles are appended as per [`extract`](#method.extract).
+    pub fn extract_dual<S>(&mut self, source: &S, vertices: &mut Vec<f32>, indices: &mut Vec<u32>)
+    where
+        S: HermiteSource,
+        R: RefinementPolicy<S>,
+    {
+        self.extract_dual_to(source, &mut VecSink::new(vertices, indices));
+    }
+
+    /// Extracts a mesh with normals from the given
+    /// [`HermiteSource`](../source/trait.HermiteSource.html), using Dual Marching Cubes.
+    ///
+    /// The dual grid is placed as per [`extract_dual`](#method.extract_dual), and vertices and
+    /// triangles are appended as per [`extract_with_normals`](#method.extract_with_normals).
+    pub fn extract_dual_with_normals<S>(
+        &mut self,
+        source: &S,
+        vertices: &mut Vec<f32>,
+        indices: &mut Vec<u32>,
+    ) where
+        S: HermiteSource,
+        R: RefinementPolicy<S>,
+    {
+        self.extract_dual_with_normals_to(source, &mut VecSink::new(vertices, indices));
+    }
+
+    /// Extracts a mesh from the given [`HermiteSource`](../source/trait.HermiteSource.html) into
+    /// the given [`MeshSink`](../sink/trait.MeshSink.html), using Dual Marching Cubes.
+    pub fn extract_dual_to<S, M>(&mut self, source: &S, sink: &mut M)
+    where
+        S: HermiteSource,
+        M: MeshSink,
+        R: RefinementPolicy<S>,
+    {
+        let region = self.region;
+        let iso_level = self.iso_level;
+        let mut leaf_vertices = HashMap::new();
+        self.extract_impl(
+            source,
+            |key: Morton, distance: f32| {
+                *leaf_vertices
+                    .entry(key)
+                    .or_insert_with(|| leaf_vertex(source, &region, iso_level, key, distance))
+            },
+            |_| None,
+            sink,
+        );
+    }
+
+    /// Extracts a mesh with normals from the given
+    /// [`HermiteSource`](../source/trait.HermiteSource.html) into the given
+    /// [`MeshSink`](../sink/trait.MeshSink.html), using Dual Marching Cubes.
+    pub fn extract_dual_with_normals_to<S, M>(&mut self, source: &S, sink: &mut M)
+    where
+        S: HermiteSource,
+        M: MeshSink,
+        R: RefinementPolicy<S>,
+    {
+        let region = self.region;
+        let iso_level = self.iso_level;
+        let mut leaf_vertices = HashMap::new();
+        self.extract_impl(
+            source,
+            |key: Morton, distance: f32| {
+                *leaf_vertices
+                    .entry(key)
+                    .or_insert_with(|| leaf_vertex(source, &region, iso_level, key, distance))
             },
-            indices,
+            |v: Vec3| Some(source.sample_normal(v.x, v.y, v.z)),
+            sink,
         );
     }
 
-    fn extract_impl<S, E>(&mut self, source: &S, extract: E, indices: &mut Vec<u32>)
+    fn extract_impl<S, P, N, M>(&mut self, source: &S, place: P, normal: N, sink: &mut M)
     where
         S: Source,
-        E: FnMut(Vec3) -> (),
+        P: FnMut(Morton, f32) -> (Vec3, f32),
+        N: Fn(Vec3) -> Option<Vec3>,
+        M: MeshSink,
+        R: RefinementPolicy<S>,
     {
         let octree = self.build_octree(source);
         let primal_vertices = self.compute_primal_vertices(&octree);
-        let mut base_index = 0;
-        self.extract_surface(&octree, &primal_vertices, indices, &mut base_index, extract);
+        let mut vertices = DualVertices {
+            place,
+            normal,
+            base_index: 0,
+        };
+        self.extract_surface(&octree, &primal_vertices, &mut vertices, sink);
     }
 
     fn build_octree<S>(&mut self, source: &S) -> LinearHashedOctree<f32>
     where
         S: Source,
+        R: RefinementPolicy<S>,
     {
         let max_depth = self.max_depth;
+        let region = self.region;
+        let iso_level = self.iso_level;
+        let refinement = &self.refinement;
         let mut octree = LinearHashedOctree::new();
+        let mut sample = |key: Morton| {
+            let p = leaf_centre(&region, key);
+            source.sample(p.x, p.y, p.z)
+        };
 
         octree.build(
             |key: Morton, distance: &f32| {
                 let level = key.level();
-                let size = key.size();
-                level < 2 || (level < max_depth && distance.abs() <= size * SQRT_OF_3)
-            },
-            |key: Morton| {
-                let p = key.center();
-                source.sample(p.x, p.y, p.z)
+                let node = OctreeNode {
+                    level,
+                    centre: leaf_centre(&region, key),
+                    half_size: region.extent * key.size(),
+                    value: distance - iso_level,
+                    iso_level,
+                };
+                level < 2 || (level < max_depth && refinement.should_refine(source, &node))
             },
+            &mut sample,
         );
+        if self.balanced {
+            octree.balance(&mut sample);
+        }
 
         octree
     }
@@ -167,15 +378,16 @@ impl LinearHashedMarchingCubes {
         primal_vertices
     }
 
-    fn extract_surface<E>(
+    fn extract_surface<P, N, M>(
         &mut self,
         octree: &LinearHashedOctree<f32>,
         primal_vertices: &HashMap<Morton, usize>,
-        indices: &mut Vec<u32>,
-        base_index: &mut u32,
-        mut extract: E,
+        vertices: &mut DualVertices<P, N>,
+        sink: &mut M,
     ) where
-        E: FnMut(Vec3) -> (),
+        P: FnMut(Morton, f32) -> (Vec3, f32),
+        N: Fn(Vec3) -> Option<Vec3>,
+        M: MeshSink,
     {
         let mut index_map = HashMap::new();
 
@@ -195,27 +407,21 @@ impl LinearHashedMarchingCubes {
                 }
             }
 
-            self.march_one_cube(
-                duals,
-                dual_distances,
-                &mut index_map,
-                indices,
-                base_index,
-                &mut extract,
-            );
+            self.march_one_cube(duals, dual_distances, vertices, &mut index_map, sink);
         }
     }
 
-    fn march_one_cube<E>(
+    fn march_one_cube<P, N, M>(
         &mut self,
         nodes: [Morton; 8],
         dual_distances: [f32; 8],
+        vertices: &mut DualVertices<P, N>,
         index_map: &mut HashMap<Edge, u32>,
-        indices: &mut Vec<u32>,
-        base_index: &mut u32,
-        extract: &mut E,
+        sink: &mut M,
     ) where
-        E: FnMut(Vec3) -> (),
+        P: FnMut(Morton, f32) -> (Vec3, f32),
+        N: Fn(Vec3) -> Option<Vec3>,
+        M: MeshSink,
     {
         let mut reordered_nodes = [Morton::with_key(0); 8];
         let mut corners = [Vec3::zero(); 8];
@@ -226,29 +432,122 @@ impl LinearHashedMarchingCubes {
             let distance = dual_distances[REMAP_CUBE[i]];
 
             reordered_nodes[i] = key;
-            corners[i] = key.center();
-            values[i] = distance;
+            let (corner, value) = (vertices.place)(key, distance);
+            corners[i] = corner;
+            values[i] = value;
         }
 
-        march_cube(&values, |edge: usize| {
+        let mut triangle = Triangle::new();
+
+        march_cube(&values, self.iso_level, |edge: usize| {
             let u = EDGE_CONNECTION[edge][0];
             let v = EDGE_CONNECTION[edge][1];
 
             let edge_key = Edge::new(reordered_nodes[u], reordered_nodes[v]);
 
-            if let Some(&index) = index_map.get(&edge_key) {
-                indices.push(index);
+            let index = if let Some(&index) = index_map.get(&edge_key) {
+                index
             } else {
-                let index = *base_index;
-                *base_index += 1;
+                let index = vertices.base_index;
+                vertices.base_index += 1;
 
                 index_map.insert(edge_key, index);
-                indices.push(index);
 
-                let offset = get_offset(values[u], values[v]);
+                let offset = get_offset(values[u], values[v], self.iso_level);
                 let vertex = interpolate(corners[u], corners[v], offset);
-                extract(vertex);
+                sink.add_vertex(vertex, (vertices.normal)(vertex));
+                index
+            };
+
+            // Where leaves of different levels meet, several corners of the dual cell belong to
+            // the same leaf, and distinct edges of the cell collapse onto the same pair of leaves.
+            // Triangles spanning those edges have no area, and would otherwise appear to share
+            // their other edges with too many neighbours.
+            if let Some([a, b, c]) = triangle.next(index) {
+                if a != b && b != c && c != a {
+                    sink.add_triangle(a, b, c);
+                }
             }
         });
     }
 }
+
+// Where the vertices of the mesh are placed, and how they are numbered
+struct DualVertices<P, N> {
+    // Positions the corner of a dual cell belonging to an octree leaf, given the distance
+    // sampled at the leaf, and returns the distance at that position
+    place: P,
+    normal: N,
+    // The index of the next vertex
+    base_index: u32,
+}
+
+// The centre of an octree leaf, within the region
+fn leaf_centre(region: &Region, key: Morton) -> Vec3 {
+    region.origin + key.center() * region.extent
+}
+
+// Place the vertex of an octree leaf at the point which best fits the tangent planes of the
+// surface within the leaf, and sample the source there. Leaves which the surface doesn't cross
+// keep their centre, and the distance already sampled there.
+fn leaf_vertex<S>(
+    source: &S,
+    region: &Region,
+    iso_level: f32,
+    key: Morton,
+    distance: f32,
+) -> (Vec3, f32)
+where
+    S: HermiteSource,
+{
+    let centre = leaf_centre(region, key);
+    let half_size = region.extent * key.size();
+    let min = centre - half_size;
+
+    let mut corners = [Vec3::zero(); 8];
+    let mut values = [0f32; 8];
+    for i in 0..8 {
+        let offset = Vec3::new(
+            CORNERS[i][0] as f32,
+            CORNERS[i][1] as f32,
+            CORNERS[i][2] as f32,
+        );
+        corners[i] = min + offset * half_size * 2.0;
+        values[i] = source.sample(corners[i].x, corners[i].y, corners[i].z);
+    }
+
+    let mut qef = Qef::new();
+    let mut crossings = 0;
+    for edge in &EDGE_CONNECTION {
+        let (u, v) = (edge[0], edge[1]);
+        if (values[u] <= iso_level) == (values[v] <= iso_level) {
+            continue;
+        }
+
+        let offset = get_offset(values[u], values[v], iso_level);
+        let p = interpolate(corners[u], corners[v], offset);
+        qef.add(p, source.sample_normal(p.x, p.y, p.z));
+        crossings += 1;
+    }
+
+    if crossings == 0 {
+        return (centre, distance);
+    }
+
+    let vertex = clamp_to_cell(qef.solve(), corners[0], corners[6]);
+
+    // The vertex lies on (or very near) the surface, so may land on either side of it. Keep it on
+    // the same side as the centre of the leaf, so that the mesh is connected exactly as it would
+    // be without moving the vertices, and stays just as watertight.
+    let value = source.sample(vertex.x, vertex.y, vertex.z);
+    let inside = distance <= iso_level;
+    let value = if (value <= iso_level) == inside {
+        value
+    } else if inside {
+        iso_level
+    } else {
+        iso_level + iso_level.abs().max(1.0) * f32::EPSILON
+    };
+
+    (vertex, value)
+}
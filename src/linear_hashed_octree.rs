@@ -12,15 +12,28 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use morton::Morton;
+use math::Vec3;
+use morton::{Morton, MAX_LEVEL};
 use std::collections::{HashMap, VecDeque};
 
+/// A sparse octree, which stores its nodes in a hash table keyed by their
+/// [`Morton`](../morton/struct.Morton.html) codes.
+///
+/// The root node spans the unit cube from (0,0,0) to (1,1,1). Every node which is split has all 8
+/// of its children present, and each node, leaf or not, carries a value of type `Node`.
 pub struct LinearHashedOctree<Node> {
     nodes: HashMap<Morton, Node>,
     leaves: Vec<Morton>,
 }
 
+impl<Node> Default for LinearHashedOctree<Node> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<Node> LinearHashedOctree<Node> {
+    /// Create a new, empty octree
     pub fn new() -> Self {
         Self {
             nodes: HashMap::new(),
@@ -28,6 +41,10 @@ impl<Node> LinearHashedOctree<Node> {
         }
     }
 
+    /// Build the octree top-down from the root node.
+    ///
+    /// `construct_node` creates the value stored at each node, and `should_refine` decides, given
+    /// that value, whether the node is split into 8 children.
     pub fn build<R, C>(&mut self, mut should_refine: R, mut construct_node: C)
     where
         R: FnMut(Morton, &Node) -> bool,
@@ -51,6 +68,46 @@ impl<Node> LinearHashedOctree<Node> {
         }
     }
 
+    /// Split leaves until no leaf is more than one level coarser than any leaf it touches, be it
+    /// across a face, an edge or a corner.
+    pub fn balance<C>(&mut self, mut construct_node: C)
+    where
+        C: FnMut(Morton) -> Node,
+    {
+        let mut queue = self.leaves.clone();
+
+        while let Some(key) = queue.pop() {
+            if !self.is_leaf(&key) {
+                continue;
+            }
+
+            let level = key.level();
+            for i in 0..27i32 {
+                let offset = [i % 3 - 1, i / 3 % 3 - 1, i / 9 - 1];
+                let neighbour = match self.find_neighbour(&key, offset) {
+                    Some(neighbour) if offset != [0, 0, 0] => neighbour,
+                    _ => continue,
+                };
+
+                if neighbour.level() + 1 < level {
+                    for j in 0..8 {
+                        let child = neighbour.child(j);
+                        let node = construct_node(child);
+                        self.nodes.insert(child, node);
+                        self.leaves.push(child);
+                        queue.push(child);
+                    }
+                    // The split may not have been enough, so check this leaf again
+                    queue.push(key);
+                }
+            }
+        }
+
+        let nodes = &self.nodes;
+        self.leaves.retain(|key| !nodes.contains_key(&key.child(0)));
+    }
+
+    /// Call `walker` with the key of every leaf
     pub fn walk_leaves<W>(&self, mut walker: W)
     where
         W: FnMut(Morton),
@@ -60,8 +117,102 @@ impl<Node> LinearHashedOctree<Node> {
         }
     }
 
+    /// Iterate over the key and value of every leaf
+    pub fn leaves<'a>(&'a self) -> impl Iterator<Item = (Morton, &'a Node)> + 'a {
+        self.leaves.iter().map(move |&key| (key, &self.nodes[&key]))
+    }
+
+    /// Iterate over the key and value of the given node, and every node below it, parents before
+    /// their children. Empty if the node isn't present.
+    pub fn subtree<'a>(&'a self, key: &Morton) -> Subtree<'a, Node> {
+        Subtree {
+            octree: self,
+            stack: if self.nodes.contains_key(key) {
+                vec![*key]
+            } else {
+                Vec::new()
+            },
+        }
+    }
+
+    /// The number of nodes in the octree, including those which aren't leaves
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Whether the octree contains no nodes at all
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Whether the given node is present and not split
+    #[inline]
+    pub fn is_leaf(&self, key: &Morton) -> bool {
+        self.nodes.contains_key(key) && !self.nodes.contains_key(&key.child(0))
+    }
+
+    /// The value stored at the given node, if it is present
     #[inline]
     pub fn get_node(&self, key: &Morton) -> Option<&Node> {
         self.nodes.get(key)
     }
+
+    /// The value stored at the given node, if it is present
+    #[inline]
+    pub fn get_node_mut(&mut self, key: &Morton) -> Option<&mut Node> {
+        self.nodes.get_mut(key)
+    }
+
+    /// The leaf containing the given point, or `None` if the point lies outside the octree.
+    pub fn locate(&self, point: Vec3) -> Option<Morton> {
+        let mut key = Morton::from_point(point, 0).filter(|key| self.nodes.contains_key(key))?;
+
+        for level in 1..MAX_LEVEL + 1 {
+            match Morton::from_point(point, level) {
+                Some(child) if self.nodes.contains_key(&child) => key = child,
+                _ => break,
+            }
+        }
+
+        Some(key)
+    }
+
+    /// The node adjacent to the given one, offset by the given number of nodes along each axis,
+    /// as per [`Morton::neighbour`](../morton/struct.Morton.html#method.neighbour).
+    ///
+    /// Where the octree isn't refined as far as the given node, this is the nearest coarser node
+    /// which contains the neighbour. `None` if the neighbour would lie outside the octree.
+    pub fn find_neighbour(&self, key: &Morton, offset: [i32; 3]) -> Option<Morton> {
+        let mut neighbour = key.neighbour(offset)?;
+        while !self.nodes.contains_key(&neighbour) {
+            if neighbour.level() == 0 {
+                return None;
+            }
+            neighbour = neighbour.parent();
+        }
+
+        Some(neighbour)
+    }
+}
+
+/// Iterates over a subtree of a [`LinearHashedOctree`](struct.LinearHashedOctree.html), as
+/// returned by [`subtree`](struct.LinearHashedOctree.html#method.subtree).
+pub struct Subtree<'a, Node: 'a> {
+    octree: &'a LinearHashedOctree<Node>,
+    stack: Vec<Morton>,
+}
+
+impl<'a, Node> Iterator for Subtree<'a, Node> {
+    type Item = (Morton, &'a Node);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let key = self.stack.pop()?;
+        if !self.octree.is_leaf(&key) {
+            for i in (0..8).rev() {
+                self.stack.push(key.child(i));
+            }
+        }
+
+        Some((key, &self.octree.nodes[&key]))
+    }
 }
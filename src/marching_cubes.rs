@@ -12,33 +12,154 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use source::{HermiteSource, Source};
-use index_cache::IndexCache;
-use marching_cubes_impl::{get_offset, interpolate, march_cube};
+use source::{BoundedSource, HermiteSource, Source};
+use index_cache::{IndexCache, EMPTY};
+use marching_cubes_impl::{edge_vertex, march, CubeVertex, Triangle};
 use marching_cubes_tables::{CORNERS, EDGE_CONNECTION};
 use math::Vec3;
+use region::Region;
+use sink::{MeshSink, VecSink};
+use std;
+use std::cmp;
+use std::ops::Range;
+use transvoxel_impl::Transitions;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+// The number of cells along each side of the bricks which are skipped by bounded extraction
+const BRICK_SIZE: usize = 8;
+
+/// A set of faces of a chunk.
+///
+/// Used to mark which faces of a chunk border a neighbouring chunk at half the resolution.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Faces(u8);
+
+impl Faces {
+    /// The face at the minimum x coordinate
+    pub const NEGATIVE_X: Faces = Faces(1);
+    /// The face at the maximum x coordinate
+    pub const POSITIVE_X: Faces = Faces(2);
+    /// The face at the minimum y coordinate
+    pub const NEGATIVE_Y: Faces = Faces(4);
+    /// The face at the maximum y coordinate
+    pub const POSITIVE_Y: Faces = Faces(8);
+    /// The face at the minimum z coordinate
+    pub const NEGATIVE_Z: Faces = Faces(16);
+    /// The face at the maximum z coordinate
+    pub const POSITIVE_Z: Faces = Faces(32);
+
+    /// The empty set of faces
+    pub fn empty() -> Faces {
+        Faces(0)
+    }
+
+    /// The set of all six faces
+    pub fn all() -> Faces {
+        Faces(63)
+    }
+
+    /// The face at the minimum coordinate along the given axis (0 for x, 1 for y, 2 for z)
+    pub fn negative(axis: usize) -> Faces {
+        Faces(1 << (axis * 2))
+    }
+
+    /// The face at the maximum coordinate along the given axis (0 for x, 1 for y, 2 for z)
+    pub fn positive(axis: usize) -> Faces {
+        Faces(2 << (axis * 2))
+    }
+
+    /// Whether the set contains no faces
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Whether the set contains all of the faces in `other`
+    pub fn contains(&self, other: Faces) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl std::ops::BitOr for Faces {
+    type Output = Faces;
+
+    fn bitor(self, other: Faces) -> Faces {
+        Faces(self.0 | other.0)
+    }
+}
+
+/// How marching cubes triangulates cells whose corners admit more than one topology
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Classification {
+    /// The classic Lorensen-Cline table. Fast, but it resolves ambiguous faces and interiors by
+    /// corner signs alone, which can give the surface an inconsistent topology where it pinches.
+    Classic,
+    /// Resolves ambiguous faces with the asymptotic decider, and ambiguous interiors with the
+    /// trilinear interpolant of the corners, aiming at the topology of Chernyaev's Marching Cubes
+    /// 33. Rather than using its tables, the contours on each face are linked into loops and
+    /// triangulated directly. Produces manifold, watertight meshes, at some extra cost in cells
+    /// containing more than one piece of surface.
+    Mc33,
+}
 
 /// Extracts meshes from distance fields using the marching cubes algorithm.
 pub struct MarchingCubes {
-    size: usize,
+    region: Region,
+    iso_level: f32,
+    classification: Classification,
     layers: [Vec<f32>; 2],
 }
 
 impl MarchingCubes {
     /// Create a new MarchingCubes with the given chunk size.
     ///
-    /// For a given `size`, this will evaluate chunks of `size^3` voxels.
+    /// For a given `size`, this will evaluate chunks of `size^3` voxels, spanning the unit cube.
     pub fn new(size: usize) -> MarchingCubes {
+        MarchingCubes::with_region(Region::unit(size))
+    }
+
+    /// Create a new MarchingCubes which samples the given [`Region`](../region/struct.Region.html).
+    pub fn with_region(region: Region) -> MarchingCubes {
+        let layer_size = region.samples[0] * region.samples[1];
         MarchingCubes {
-            size,
-            layers: [vec![0f32; size * size], vec![0f32; size * size]],
+            region,
+            iso_level: 0.0,
+            classification: Classification::Classic,
+            layers: [vec![0f32; layer_size], vec![0f32; layer_size]],
         }
     }
 
+    /// Set the iso-level at which to extract the surface.
+    ///
+    /// Defaults to zero, which is the surface of a signed distance field. Samples at or below the
+    /// iso-level are considered to be inside the surface.
+    pub fn set_iso_level(&mut self, iso_level: f32) {
+        self.iso_level = iso_level;
+    }
+
+    /// The iso-level at which the surface is extracted
+    pub fn iso_level(&self) -> f32 {
+        self.iso_level
+    }
+
+    /// Set how cells with ambiguous topology are triangulated.
+    ///
+    /// Defaults to [`Classification::Classic`](enum.Classification.html). Transition cells are
+    /// unaffected, and always use their own table.
+    pub fn set_classification(&mut self, classification: Classification) {
+        self.classification = classification;
+    }
+
+    /// How cells with ambiguous topology are triangulated
+    pub fn classification(&self) -> Classification {
+        self.classification
+    }
+
     /// Extracts a mesh from the given [`Source`](../source/trait.Source.html).
     ///
-    /// The Source will be sampled in the range (0,0,0) to (1,1,1), with the number of steps
-    /// determined by the size provided to the constructor.
+    /// The Source will be sampled across the region provided to the constructor, which defaults
+    /// to the range (0,0,0) to (1,1,1), and extracted vertices will lie within that region.
     ///
     /// Extracted vertices will be appended to `vertices` as triples of (x, y, z)
     /// coordinates. Extracted triangles will be appended to `indices` as triples of
@@ -47,21 +168,13 @@ impl MarchingCubes {
     where
         S: Source,
     {
-        self.extract_impl(
-            source,
-            |v: Vec3| {
-                vertices.push(v.x);
-                vertices.push(v.y);
-                vertices.push(v.z);
-            },
-            indices,
-        );
+        self.extract_to(source, &mut VecSink::new(vertices, indices));
     }
 
     /// Extracts a mesh from the given [`HermiteSource`](../source/trait.HermiteSource.html).
     ///
-    /// The Source will be sampled in the range (0,0,0) to (1,1,1), with the number of steps
-    /// determined by the size provided to the constructor.
+    /// The Source will be sampled across the region provided to the constructor, which defaults
+    /// to the range (0,0,0) to (1,1,1), and extracted vertices will lie within that region.
     ///
     /// Extracted vertices will be appended to `vertices` as triples of (x, y, z)
     /// coordinates, followed by the surface normals as triples of (x, y, z) dimensions. Extracted
@@ -73,93 +186,767 @@ impl MarchingCubes {
         indices: &mut Vec<u32>,
     ) where
         S: HermiteSource,
+    {
+        self.extract_with_normals_to(source, &mut VecSink::new(vertices, indices));
+    }
+
+    /// Extracts a mesh from the given [`Source`](../source/trait.Source.html), with transition
+    /// cells along the given faces.
+    ///
+    /// Each face in `transitions` will be stitched to a neighbouring chunk of half the resolution
+    /// (i.e. a chunk covering twice the area, extracted with the same size), using transition
+    /// cells laid out as in the Transvoxel algorithm. The regular cells along those faces are
+    /// squashed slightly to make room for the transition cells. The number of samples along each
+    /// axis must be odd when `transitions` is not empty.
+    ///
+    /// Vertices and triangles are appended as per [`extract`](#method.extract).
+    pub fn extract_with_transitions<S>(
+        &mut self,
+        source: &S,
+        transitions: Faces,
+        vertices: &mut Vec<f32>,
+        indices: &mut Vec<u32>,
+    ) where
+        S: Source,
+    {
+        self.extract_with_transitions_to(source, transitions, &mut VecSink::new(vertices, indices));
+    }
+
+    /// Extracts a mesh from the given [`HermiteSource`](../source/trait.HermiteSource.html), with
+    /// transition cells along the given faces.
+    ///
+    /// Transition cells are generated as per
+    /// [`extract_with_transitions`](#method.extract_with_transitions), and vertices and triangles
+    /// are appended as per [`extract_with_normals`](#method.extract_with_normals).
+    pub fn extract_with_transitions_and_normals<S>(
+        &mut self,
+        source: &S,
+        transitions: Faces,
+        vertices: &mut Vec<f32>,
+        indices: &mut Vec<u32>,
+    ) where
+        S: HermiteSource,
+    {
+        self.extract_with_transitions_and_normals_to(
+            source,
+            transitions,
+            &mut VecSink::new(vertices, indices),
+        );
+    }
+
+    /// Extracts a mesh from the given [`Source`](../source/trait.Source.html) into the given
+    /// [`MeshSink`](../sink/trait.MeshSink.html).
+    pub fn extract_to<S, M>(&mut self, source: &S, sink: &mut M)
+    where
+        S: Source,
+        M: MeshSink,
+    {
+        self.extract_with_transitions_to(source, Faces::empty(), sink);
+    }
+
+    /// Extracts a mesh with normals from the given [`HermiteSource`](../source/trait.HermiteSource.html)
+    /// into the given [`MeshSink`](../sink/trait.MeshSink.html).
+    pub fn extract_with_normals_to<S, M>(&mut self, source: &S, sink: &mut M)
+    where
+        S: HermiteSource,
+        M: MeshSink,
+    {
+        self.extract_with_transitions_and_normals_to(source, Faces::empty(), sink);
+    }
+
+    /// Extracts a mesh from the given [`Source`](../source/trait.Source.html) into the given
+    /// [`MeshSink`](../sink/trait.MeshSink.html), with transition cells along the given faces.
+    pub fn extract_with_transitions_to<S, M>(
+        &mut self,
+        source: &S,
+        transitions: Faces,
+        sink: &mut M,
+    ) where
+        S: Source,
+        M: MeshSink,
+    {
+        self.extract_impl(source, transitions, |_| None, sink);
+    }
+
+    /// Extracts a mesh with normals from the given [`HermiteSource`](../source/trait.HermiteSource.html)
+    /// into the given [`MeshSink`](../sink/trait.MeshSink.html), with transition cells along the
+    /// given faces.
+    pub fn extract_with_transitions_and_normals_to<S, M>(
+        &mut self,
+        source: &S,
+        transitions: Faces,
+        sink: &mut M,
+    ) where
+        S: HermiteSource,
+        M: MeshSink,
     {
         self.extract_impl(
             source,
-            |v: Vec3| {
-                let n = source.sample_normal(v.x, v.y, v.z);
-                vertices.push(v.x);
-                vertices.push(v.y);
-                vertices.push(v.z);
-                vertices.push(n.x);
-                vertices.push(n.y);
-                vertices.push(n.z);
-            },
-            indices,
+            transitions,
+            |v: Vec3| Some(source.sample_normal(v.x, v.y, v.z)),
+            sink,
+        );
+    }
+
+    /// Extracts a mesh from the given [`BoundedSource`](../source/trait.BoundedSource.html),
+    /// skipping the parts of the region which the surface can't pass through.
+    ///
+    /// The region is divided into bricks of 8x8x8 cells, and the Source is bounded over each
+    /// brick. Bricks whose bounds exclude the iso-level are empty, and points which only lie in
+    /// empty bricks are never sampled. The mesh is identical to the one produced by
+    /// [`extract`](#method.extract), but far fewer samples are taken when most of the region is
+    /// far from the surface.
+    ///
+    /// Vertices and triangles are appended as per [`extract`](#method.extract).
+    pub fn extract_bounded<S>(
+        &mut self,
+        source: &S,
+        vertices: &mut Vec<f32>,
+        indices: &mut Vec<u32>,
+    ) where
+        S: BoundedSource,
+    {
+        self.extract_bounded_to(source, &mut VecSink::new(vertices, indices));
+    }
+
+    /// Extracts a mesh with normals from the given
+    /// [`BoundedSource`](../source/trait.BoundedSource.html), skipping the parts of the region
+    /// which the surface can't pass through.
+    ///
+    /// Empty space is skipped as per [`extract_bounded`](#method.extract_bounded), and vertices
+    /// and triangles are appended as per [`extract_with_normals`](#method.extract_with_normals).
+    pub fn extract_bounded_with_normals<S>(
+        &mut self,
+        source: &S,
+        vertices: &mut Vec<f32>,
+        indices: &mut Vec<u32>,
+    ) where
+        S: BoundedSource + HermiteSource,
+    {
+        self.extract_bounded_with_normals_to(source, &mut VecSink::new(vertices, indices));
+    }
+
+    /// Extracts a mesh from the given [`BoundedSource`](../source/trait.BoundedSource.html) into
+    /// the given [`MeshSink`](../sink/trait.MeshSink.html), skipping the parts of the region which
+    /// the surface can't pass through.
+    pub fn extract_bounded_to<S, M>(&mut self, source: &S, sink: &mut M)
+    where
+        S: BoundedSource,
+        M: MeshSink,
+    {
+        let bricks = Bricks::new(source, &self.region, self.iso_level);
+        self.extract_impl(&bricks, Faces::empty(), |_| None, sink);
+    }
+
+    /// Extracts a mesh with normals from the given
+    /// [`BoundedSource`](../source/trait.BoundedSource.html) into the given
+    /// [`MeshSink`](../sink/trait.MeshSink.html), skipping the parts of the region which the
+    /// surface can't pass through.
+    pub fn extract_bounded_with_normals_to<S, M>(&mut self, source: &S, sink: &mut M)
+    where
+        S: BoundedSource + HermiteSource,
+        M: MeshSink,
+    {
+        let bricks = Bricks::new(source, &self.region, self.iso_level);
+        self.extract_impl(
+            &bricks,
+            Faces::empty(),
+            |v: Vec3| Some(source.sample_normal(v.x, v.y, v.z)),
+            sink,
         );
     }
 
-    fn extract_impl<S, E>(&mut self, source: &S, mut extract: E, indices: &mut Vec<u32>)
+    /// Extracts the surfaces at each of the given iso-levels from the given
+    /// [`Source`](../source/trait.Source.html), in a single pass over the region.
+    ///
+    /// The Source is sampled only once, no matter how many iso-levels are requested, which makes
+    /// this considerably cheaper than extracting each surface separately when the Source is
+    /// expensive to evaluate. The iso-level set by [`set_iso_level`](#method.set_iso_level) is
+    /// ignored.
+    ///
+    /// Vertices are appended as per [`extract`](#method.extract). The triangles of each surface
+    /// are appended to `indices` one surface after another, and the range of `indices` holding
+    /// the triangles of each surface is returned, in the same order as `iso_levels`.
+    pub fn extract_levels<S>(
+        &mut self,
+        source: &S,
+        iso_levels: &[f32],
+        vertices: &mut Vec<f32>,
+        indices: &mut Vec<u32>,
+    ) -> Vec<Range<usize>>
     where
         S: Source,
-        E: FnMut(Vec3) -> (),
     {
-        let size_minus_one = self.size - 1;
-        let one_over_size = 1.0 / (size_minus_one as f32);
+        let start = indices.len();
+        let mut sink = VecSink::new(vertices, indices);
+        self.extract_levels_to(source, iso_levels, &mut sink)
+            .into_iter()
+            .map(|r| start + r.start * 3..start + r.end * 3)
+            .collect()
+    }
 
-        // Cache layer zero of distance field values
-        for y in 0usize..self.size {
-            for x in 0..self.size {
-                self.layers[0][y * self.size + x] =
-                    source.sample(x as f32 * one_over_size, y as f32 * one_over_size, 0.0);
+    /// Extracts the surfaces at each of the given iso-levels from the given
+    /// [`HermiteSource`](../source/trait.HermiteSource.html), in a single pass over the region.
+    ///
+    /// Surfaces are extracted as per [`extract_levels`](#method.extract_levels), and vertices are
+    /// appended as per [`extract_with_normals`](#method.extract_with_normals).
+    pub fn extract_levels_with_normals<S>(
+        &mut self,
+        source: &S,
+        iso_levels: &[f32],
+        vertices: &mut Vec<f32>,
+        indices: &mut Vec<u32>,
+    ) -> Vec<Range<usize>>
+    where
+        S: HermiteSource,
+    {
+        let start = indices.len();
+        let mut sink = VecSink::new(vertices, indices);
+        self.extract_levels_with_normals_to(source, iso_levels, &mut sink)
+            .into_iter()
+            .map(|r| start + r.start * 3..start + r.end * 3)
+            .collect()
+    }
+
+    /// Extracts the surfaces at each of the given iso-levels from the given
+    /// [`Source`](../source/trait.Source.html) into the given
+    /// [`MeshSink`](../sink/trait.MeshSink.html), in a single pass over the region.
+    ///
+    /// Vertices are passed to the sink as they are generated, but the triangles are held back
+    /// until the whole region has been processed, and then passed to the sink one surface after
+    /// another. Returns the range of triangles belonging to each surface, numbered from zero in
+    /// the order they were passed to the sink.
+    pub fn extract_levels_to<S, M>(
+        &mut self,
+        source: &S,
+        iso_levels: &[f32],
+        sink: &mut M,
+    ) -> Vec<Range<usize>>
+    where
+        S: Source,
+        M: MeshSink,
+    {
+        self.extract_levels_impl(source, iso_levels, |_| None, sink)
+    }
+
+    /// Extracts the surfaces at each of the given iso-levels from the given
+    /// [`HermiteSource`](../source/trait.HermiteSource.html) into the given
+    /// [`MeshSink`](../sink/trait.MeshSink.html), in a single pass over the region.
+    ///
+    /// Surfaces are passed to the sink as per [`extract_levels_to`](#method.extract_levels_to).
+    pub fn extract_levels_with_normals_to<S, M>(
+        &mut self,
+        source: &S,
+        iso_levels: &[f32],
+        sink: &mut M,
+    ) -> Vec<Range<usize>>
+    where
+        S: HermiteSource,
+        M: MeshSink,
+    {
+        self.extract_levels_impl(
+            source,
+            iso_levels,
+            |v: Vec3| Some(source.sample_normal(v.x, v.y, v.z)),
+            sink,
+        )
+    }
+
+    /// Extracts a mesh from the given [`Source`](../source/trait.Source.html), using every thread
+    /// in the current rayon thread pool.
+    ///
+    /// The chunk is split into slabs of layers, which are extracted in parallel, and stitched
+    /// back together so that vertices along the seams between slabs are shared. The resulting
+    /// mesh is identical to that produced by [`extract`](#method.extract).
+    ///
+    /// Requires the `rayon` feature.
+    #[cfg(feature = "rayon")]
+    pub fn par_extract<S>(&self, source: &S, vertices: &mut Vec<f32>, indices: &mut Vec<u32>)
+    where
+        S: Source + Sync,
+    {
+        self.par_extract_to(source, &mut VecSink::new(vertices, indices));
+    }
+
+    /// Extracts a mesh from the given [`HermiteSource`](../source/trait.HermiteSource.html),
+    /// using every thread in the current rayon thread pool.
+    ///
+    /// The resulting mesh is identical to that produced by
+    /// [`extract_with_normals`](#method.extract_with_normals).
+    ///
+    /// Requires the `rayon` feature.
+    #[cfg(feature = "rayon")]
+    pub fn par_extract_with_normals<S>(
+        &self,
+        source: &S,
+        vertices: &mut Vec<f32>,
+        indices: &mut Vec<u32>,
+    ) where
+        S: HermiteSource + Sync,
+    {
+        self.par_extract_with_normals_to(source, &mut VecSink::new(vertices, indices));
+    }
+
+    /// Extracts a mesh from the given [`Source`](../source/trait.Source.html) into the given
+    /// [`MeshSink`](../sink/trait.MeshSink.html), using every thread in the current rayon thread
+    /// pool.
+    ///
+    /// Requires the `rayon` feature.
+    #[cfg(feature = "rayon")]
+    pub fn par_extract_to<S, M>(&self, source: &S, sink: &mut M)
+    where
+        S: Source + Sync,
+        M: MeshSink,
+    {
+        self.par_extract_impl(source, |_| None, sink);
+    }
+
+    /// Extracts a mesh with normals from the given [`HermiteSource`](../source/trait.HermiteSource.html)
+    /// into the given [`MeshSink`](../sink/trait.MeshSink.html), using every thread in the current
+    /// rayon thread pool.
+    ///
+    /// Requires the `rayon` feature.
+    #[cfg(feature = "rayon")]
+    pub fn par_extract_with_normals_to<S, M>(&self, source: &S, sink: &mut M)
+    where
+        S: HermiteSource + Sync,
+        M: MeshSink,
+    {
+        self.par_extract_impl(
+            source,
+            |v: Vec3| Some(source.sample_normal(v.x, v.y, v.z)),
+            sink,
+        );
+    }
+
+    fn extract_impl<S, N, M>(&mut self, source: &S, transitions: Faces, normal: N, sink: &mut M)
+    where
+        S: Source,
+        N: Fn(Vec3) -> Option<Vec3>,
+        M: MeshSink,
+    {
+        let [size_x, size_y, size_z] = self.region.samples;
+        assert!(
+            transitions.is_empty() || (size_x % 2 == 1 && size_y % 2 == 1 && size_z % 2 == 1),
+            "transition cells require an odd number of samples along each axis"
+        );
+
+        let mut extraction = Extraction {
+            region: self.region,
+            classification: self.classification,
+            layers: &mut self.layers,
+            surfaces: vec![Surface::new(self.iso_level, size_x, size_y, false)],
+            transition_cells: Transitions::new(transitions, self.region, self.iso_level),
+            index: 0,
+        };
+        extraction.extract_layers(source, 0..size_z - 1, &normal, sink);
+
+        extraction
+            .transition_cells
+            .extract(source, &mut extraction.index, &normal, sink);
+    }
+
+    fn extract_levels_impl<S, N, M>(
+        &mut self,
+        source: &S,
+        iso_levels: &[f32],
+        normal: N,
+        sink: &mut M,
+    ) -> Vec<Range<usize>>
+    where
+        S: Source,
+        N: Fn(Vec3) -> Option<Vec3>,
+        M: MeshSink,
+    {
+        let [size_x, size_y, size_z] = self.region.samples;
+
+        let mut extraction = Extraction {
+            region: self.region,
+            classification: self.classification,
+            layers: &mut self.layers,
+            surfaces: iso_levels
+                .iter()
+                .map(|&iso_level| Surface::new(iso_level, size_x, size_y, true))
+                .collect(),
+            transition_cells: Transitions::new(Faces::empty(), self.region, self.iso_level),
+            index: 0,
+        };
+        extraction.extract_layers(source, 0..size_z - 1, &normal, sink);
+
+        let mut ranges = Vec::with_capacity(iso_levels.len());
+        let mut start = 0;
+        for surface in extraction.surfaces {
+            let level = surface.held.unwrap_or_default();
+            for &[a, b, c] in &level {
+                sink.add_triangle(a, b, c);
             }
+            ranges.push(start..start + level.len());
+            start += level.len();
         }
+        ranges
+    }
 
-        let mut corners = [Vec3::zero(); 8];
-        let mut values = [0f32; 8];
+    #[cfg(feature = "rayon")]
+    fn par_extract_impl<S, N, M>(&self, source: &S, normal: N, sink: &mut M)
+    where
+        S: Source + Sync,
+        N: Fn(Vec3) -> Option<Vec3> + Sync,
+        M: MeshSink,
+    {
+        let region = self.region;
+        let iso_level = self.iso_level;
+        let classification = self.classification;
+        let [size_x, size_y, size_z] = region.samples;
+        let cells_z = size_z - 1;
+        let slab_count = cmp::min(cells_z, rayon::current_num_threads() * SLABS_PER_THREAD);
+        let slab_layers = cells_z.div_ceil(slab_count);
+        let slab_count = cells_z.div_ceil(slab_layers);
 
-        let mut index_cache = IndexCache::new(self.size);
-        let mut index = 0u32;
+        let slabs: Vec<Slab> = (0..slab_count)
+            .into_par_iter()
+            .map(|slab| {
+                let start = slab * slab_layers;
+                let end = cmp::min(start + slab_layers, cells_z);
+                let mut layers = [vec![0f32; size_x * size_y], vec![0f32; size_x * size_y]];
+                let mut extraction = Extraction {
+                    region,
+                    classification,
+                    layers: &mut layers,
+                    surfaces: vec![Surface::new(iso_level, size_x, size_y, false)],
+                    transition_cells: Transitions::new(Faces::empty(), region, iso_level),
+                    index: 0,
+                };
+                let mut slab = Slab::default();
 
-        for z in 0..self.size {
-            // Cache layer N+1 of isosurface values
-            for y in 0..self.size {
-                for x in 0..self.size {
-                    self.layers[1][y * self.size + x] = source.sample(
-                        x as f32 * one_over_size,
-                        y as f32 * one_over_size,
-                        (z + 1) as f32 * one_over_size,
-                    );
+                // The vertices on the bottom plane belong to the slab below, so refer to them by
+                // their position on the plane until the slabs are stitched together.
+                if start > 0 {
+                    let bottom_plane = extraction.surfaces[0].index_cache.bottom_plane_mut();
+                    for (i, edges) in bottom_plane.iter_mut().enumerate() {
+                        edges[0] = SEAM | (i * 2) as u32;
+                        edges[1] = SEAM | (i * 2 + 1) as u32;
+                    }
                 }
+
+                extraction.extract_layers(source, start..end, &normal, &mut slab);
+
+                slab.top = extraction.surfaces[0].index_cache.bottom_plane().to_vec();
+                slab
+            })
+            .collect();
+
+        let mut offset = 0u32;
+        let mut below: &[[u32; 2]] = &[];
+        let mut below_offset = 0u32;
+
+        for slab in &slabs {
+            for &(vertex, normal) in &slab.vertices {
+                sink.add_vertex(vertex, normal);
+            }
+
+            let resolve = |index: u32| {
+                if index & SEAM != 0 {
+                    let edge = (index & !SEAM) as usize;
+                    below_offset + below[edge / 2][edge % 2]
+                } else {
+                    offset + index
+                }
+            };
+            for triangle in &slab.triangles {
+                sink.add_triangle(
+                    resolve(triangle[0]),
+                    resolve(triangle[1]),
+                    resolve(triangle[2]),
+                );
             }
 
-            // Extract the calls in the current layer
-            for y in 0..size_minus_one {
-                for x in 0..size_minus_one {
+            below = &slab.top;
+            below_offset = offset;
+            offset += slab.vertices.len() as u32;
+        }
+    }
+}
+
+// A surface extracted at a single iso-level
+struct Surface {
+    iso_level: f32,
+    index_cache: IndexCache,
+    triangle: Triangle,
+    // Triangles held back until the whole region has been extracted, or None to pass them
+    // straight to the sink
+    held: Option<Vec<[u32; 3]>>,
+}
+
+impl Surface {
+    fn new(iso_level: f32, size_x: usize, size_y: usize, hold_triangles: bool) -> Surface {
+        Surface {
+            iso_level,
+            index_cache: IndexCache::new(size_x, size_y),
+            triangle: Triangle::new(),
+            held: if hold_triangles {
+                Some(Vec::new())
+            } else {
+                None
+            },
+        }
+    }
+}
+
+// Add the next vertex index of a surface, passing the triangle on once it is complete
+fn push_index<M>(
+    triangle: &mut Triangle,
+    held: &mut Option<Vec<[u32; 3]>>,
+    index: u32,
+    sink: &mut M,
+) where
+    M: MeshSink,
+{
+    match *held {
+        Some(ref mut held) => {
+            if let Some(triangle) = triangle.next(index) {
+                held.push(triangle);
+            }
+        }
+        None => triangle.push(index, sink),
+    }
+}
+
+// The state carried from one layer of cells to the next while extracting a chunk
+struct Extraction<'a> {
+    region: Region,
+    classification: Classification,
+    // The distance field values on the planes below and above the current layer
+    layers: &'a mut [Vec<f32>; 2],
+    // Every surface is extracted from the same samples, but has its own vertices
+    surfaces: Vec<Surface>,
+    transition_cells: Transitions,
+    // The index of the next vertex
+    index: u32,
+}
+
+impl<'a> Extraction<'a> {
+    // Extract the cells in the given range of layers.
+    //
+    // The index caches must already hold the vertices on the bottom plane of the first layer, if
+    // any. On return, they hold the vertices on the top plane of the last layer.
+    fn extract_layers<S, N, M>(&mut self, source: &S, range: Range<usize>, normal: &N, sink: &mut M)
+    where
+        S: Source,
+        N: Fn(Vec3) -> Option<Vec3>,
+        M: MeshSink,
+    {
+        let region = &self.region;
+        let classification = self.classification;
+        let layers = &mut *self.layers;
+        let transition_cells = &mut self.transition_cells;
+        let index = &mut self.index;
+
+        let [size_x, size_y, _] = region.samples;
+        let origin = region.origin;
+        let step = region.step();
+
+        // Cache the first layer of distance field values
+        source.sample_layer(region, range.start, &mut layers[0]);
+
+        let mut corners = [Vec3::zero(); 8];
+        let mut values = [0f32; 8];
+
+        for z in range {
+            // Cache layer N+1 of isosurface values
+            source.sample_layer(region, z + 1, &mut layers[1]);
+
+            // Extract the cells in the current layer, once for each surface
+            for y in 0..size_y - 1 {
+                for x in 0..size_x - 1 {
                     for i in 0..8 {
                         corners[i] = Vec3::new(
-                            (x + CORNERS[i][0]) as f32 * one_over_size,
-                            (y + CORNERS[i][1]) as f32 * one_over_size,
-                            (z + CORNERS[i][2]) as f32 * one_over_size,
+                            origin.x + (x + CORNERS[i][0]) as f32 * step.x,
+                            origin.y + (y + CORNERS[i][1]) as f32 * step.y,
+                            origin.z + (z + CORNERS[i][2]) as f32 * step.z,
                         );
-                        values[i] = self.layers[CORNERS[i][2]]
-                            [(y + CORNERS[i][1]) * self.size + x + CORNERS[i][0]];
+                        values[i] =
+                            layers[CORNERS[i][2]][(y + CORNERS[i][1]) * size_x + x + CORNERS[i][0]];
                     }
 
-                    march_cube(&values, |edge: usize| {
-                        let cached_index = index_cache.get(x, y, edge);
-                        if cached_index > 0 {
-                            indices.push(cached_index);
-                        } else {
-                            let u = EDGE_CONNECTION[edge][0];
-                            let v = EDGE_CONNECTION[edge][1];
-
-                            index_cache.put(x, y, edge, index);
-                            indices.push(index);
-                            index += 1;
-
-                            let offset = get_offset(values[u], values[v]);
-                            let vertex = interpolate(corners[u], corners[v], offset);
-                            extract(vertex);
-                        }
+                    for surface in &mut self.surfaces {
+                        let Surface {
+                            iso_level,
+                            ref mut index_cache,
+                            ref mut triangle,
+                            ref mut held,
+                        } = *surface;
+
+                        let mut interior = [EMPTY; 16];
+                        march(classification, &values, iso_level, |vertex: CubeVertex| {
+                            let edge = match vertex {
+                                CubeVertex::Edge(edge) => edge,
+                                CubeVertex::Interior(i, position) => {
+                                    if interior[i] == EMPTY {
+                                        let vertex =
+                                            transition_cells.shrink(corners[0] + position * step);
+                                        sink.add_vertex(vertex, normal(vertex));
+                                        interior[i] = *index;
+                                        *index += 1;
+                                    }
+                                    push_index(triangle, held, interior[i], sink);
+                                    return;
+                                }
+                            };
+
+                            let cached_index = index_cache.get(x, y, edge);
+                            if cached_index != EMPTY {
+                                push_index(triangle, held, cached_index, sink);
+                            } else {
+                                let u = EDGE_CONNECTION[edge][0];
+                                let v = EDGE_CONNECTION[edge][1];
+
+                                index_cache.put(x, y, edge, *index);
+                                transition_cells.put(
+                                    [x + CORNERS[u][0], y + CORNERS[u][1], z + CORNERS[u][2]],
+                                    [x + CORNERS[v][0], y + CORNERS[v][1], z + CORNERS[v][2]],
+                                    *index,
+                                );
+                                let vertex = edge_vertex(
+                                    corners[u], corners[v], values[u], values[v], iso_level,
+                                );
+                                let vertex = transition_cells.shrink(vertex);
+                                sink.add_vertex(vertex, normal(vertex));
+                                push_index(triangle, held, *index, sink);
+                                *index += 1;
+                            }
+                        });
+                    }
+                }
+            }
+            for surface in &mut self.surfaces {
+                surface.index_cache.advance_layer();
+            }
+
+            layers.swap(0, 1);
+        }
+    }
+}
+
+// Splitting the chunk into a few slabs per thread helps balance the load, since the surface is
+// rarely spread evenly across the chunk.
+#[cfg(feature = "rayon")]
+const SLABS_PER_THREAD: usize = 4;
+
+// Flags a vertex index as referring to an edge on the bottom plane of a slab
+#[cfg(feature = "rayon")]
+const SEAM: u32 = 1 << 31;
+
+// Wraps a BoundedSource, to skip sampling the points which only lie within bricks of cells that
+// the surface can't pass through. Those points are given a value on the same side of the
+// iso-level as the rest of their brick, so that no cell in an empty brick contains the surface.
+struct Bricks<'a, S: 'a> {
+    source: &'a S,
+    counts: [usize; 3],
+    // The value for the points of each brick, or None if the surface may pass through the brick
+    bricks: Vec<Option<f32>>,
+}
+
+impl<'a, S: BoundedSource> Bricks<'a, S> {
+    fn new(source: &'a S, region: &Region, iso_level: f32) -> Self {
+        let mut cells = [0; 3];
+        let mut counts = [0; 3];
+        for axis in 0..3 {
+            cells[axis] = region.samples[axis] - 1;
+            counts[axis] = cells[axis].div_ceil(BRICK_SIZE);
+        }
+
+        let mut bricks = Vec::with_capacity(counts[0] * counts[1] * counts[2]);
+        for z in 0..counts[2] {
+            for y in 0..counts[1] {
+                for x in 0..counts[0] {
+                    let min = region.position(x * BRICK_SIZE, y * BRICK_SIZE, z * BRICK_SIZE);
+                    let max = region.position(
+                        cmp::min((x + 1) * BRICK_SIZE, cells[0]),
+                        cmp::min((y + 1) * BRICK_SIZE, cells[1]),
+                        cmp::min((z + 1) * BRICK_SIZE, cells[2]),
+                    );
+                    let interval = source.sample_interval(min, max);
+                    bricks.push(if interval.min > iso_level {
+                        Some(interval.min)
+                    } else if interval.max < iso_level {
+                        Some(interval.max)
+                    } else {
+                        None
                     });
-                    index_cache.advance_cell();
                 }
-                index_cache.advance_row();
             }
-            index_cache.advance_layer();
+        }
+
+        Self {
+            source,
+            counts,
+            bricks,
+        }
+    }
+
+    // The value of a point which only lies within empty bricks, or None if it must be sampled
+    fn empty_value(&self, point: [usize; 3]) -> Option<f32> {
+        let mut first = [0; 3];
+        let mut last = [0; 3];
+        for axis in 0..3 {
+            // Points on the boundary between bricks lie within the bricks either side
+            first[axis] = (cmp::max(point[axis], 1) - 1) / BRICK_SIZE;
+            last[axis] = cmp::min(point[axis] / BRICK_SIZE, self.counts[axis] - 1);
+        }
+
+        let mut value = None;
+        for z in first[2]..last[2] + 1 {
+            for y in first[1]..last[1] + 1 {
+                for x in first[0]..last[0] + 1 {
+                    value = Some(self.bricks[(z * self.counts[1] + y) * self.counts[0] + x]?);
+                }
+            }
+        }
+        value
+    }
+}
+
+impl<'a, S: BoundedSource> Source for Bricks<'a, S> {
+    fn sample(&self, x: f32, y: f32, z: f32) -> f32 {
+        self.source.sample(x, y, z)
+    }
+
+    fn sample_layer(&self, region: &Region, z: usize, values: &mut [f32]) {
+        let [size_x, size_y, _] = region.samples;
+        let origin = region.origin;
+        let step = region.step();
 
-            self.layers.swap(0, 1);
+        for y in 0..size_y {
+            for x in 0..size_x {
+                values[y * size_x + x] = match self.empty_value([x, y, z]) {
+                    Some(value) => value,
+                    None => self.source.sample(
+                        origin.x + x as f32 * step.x,
+                        origin.y + y as f32 * step.y,
+                        origin.z + z as f32 * step.z,
+                    ),
+                };
+            }
         }
     }
 }
+
+/// The mesh extracted from a slab of layers, with indices relative to the slab
+#[cfg(feature = "rayon")]
+#[derive(Default)]
+struct Slab {
+    vertices: Vec<(Vec3, Option<Vec3>)>,
+    triangles: Vec<[u32; 3]>,
+    top: Vec<[u32; 2]>,
+}
+
+#[cfg(feature = "rayon")]
+impl MeshSink for Slab {
+    fn add_vertex(&mut self, position: Vec3, normal: Option<Vec3>) {
+        self.vertices.push((position, normal));
+    }
+
+    fn add_triangle(&mut self, a: u32, b: u32, c: u32) {
+        self.triangles.push([a, b, c]);
+    }
+}
@@ -12,28 +12,34 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use marching_cubes_tables::TRIANGLE_CONNECTION;
+use marching_cubes::Classification;
+use marching_cubes_tables::{
+    CORNERS, EDGE_CONNECTION, FACE_CORNERS, FACE_EDGES, TRIANGLE_CONNECTION,
+};
+use math::Vec3;
+use sink::MeshSink;
 use std::ops::{Add, Mul};
 
-/// March a single cube, given the 8 corner vertices, and the density at each vertex.
+/// March a single cube, given the density at each of its 8 corners, and the iso-level at which
+/// to extract the surface.
 ///
 /// The `edge_func` will be invoked once for each vertex in the resulting mesh data, with the index
 /// of the edge on which the vertex falls. Each triplet of invocations forms one triangle.
 ///
 /// It would in many ways be simple to output triangles directly, but callers needing to produce
 /// indexed geometry will want to deduplicate vertices before forming triangles.
-pub fn march_cube<E>(values: &[f32; 8], mut edge_func: E)
+pub fn march_cube<E>(values: &[f32; 8], iso_level: f32, mut edge_func: E)
 where
     E: FnMut(usize) -> (),
 {
     // We need to construct an index into the TRIANGLE_CONNECTION table.
     // This is the table of possible triangulation topologies that is the
     // signature of marching cubes. Note that value of our source at each vertex
-    // is <= 0 or it isn't. So there are 2^8 = 256 possible combinations of vertices
-    // that are <= 0. Therefore are 256 entries in the triangle connection table
+    // is <= iso_level or it isn't. So there are 2^8 = 256 possible combinations of vertices
+    // that are <= iso_level. Therefore are 256 entries in the triangle connection table
     let mut cube_index = 0;
     for i in 0..8 {
-        if values[i] <= 0.0 {
+        if values[i] <= iso_level {
             cube_index |= 1 << i;
         }
     }
@@ -67,13 +73,569 @@ where
     }
 }
 
-/// Calculate the position of the vertex along an edge, given the density at either end of the edge.
-pub fn get_offset(a: f32, b: f32) -> f32 {
+/// A vertex of a triangle produced by marching a single cube
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum CubeVertex {
+    /// The vertex on the given edge of the cube
+    Edge(usize),
+    /// A vertex inside the cube, which is not shared with any other cube. Each is numbered
+    /// uniquely within the cube, and positioned relative to the cube, which spans (0,0,0) to
+    /// (1,1,1).
+    Interior(usize, Vec3),
+}
+
+/// March a single cube as per `march_cube`, resolving ambiguous cases according to the given
+/// classification.
+pub fn march<E>(
+    classification: Classification,
+    values: &[f32; 8],
+    iso_level: f32,
+    mut vertex_func: E,
+) where
+    E: FnMut(CubeVertex),
+{
+    match classification {
+        Classification::Classic => march_cube(values, iso_level, |edge| {
+            vertex_func(CubeVertex::Edge(edge))
+        }),
+        Classification::Mc33 => march_cube_33(values, iso_level, vertex_func),
+    }
+}
+
+/// March a single cube, resolving ambiguous cases to match the topology of the trilinear
+/// interpolant of the corners.
+///
+/// This aims at the same topology as Chernyaev's Marching Cubes 33, but doesn't use its tables.
+/// Instead, the contours on each face are linked into loops around the cube. Ambiguous faces are
+/// resolved with the asymptotic decider, so the cubes either side of a face always agree on its
+/// contours. Where a cube contains more than one loop, the interior of the cube is swept to find
+/// which loops bound the same piece of surface. Each loop which bounds a piece of its own is
+/// triangulated as a disc, and each pair bounding the same piece is joined by a tunnel. A tunnel
+/// which can't be triangulated falls back to a disc across each of its loops, so the surface is
+/// always closed.
+///
+/// The `vertex_func` is invoked as per the `edge_func` of `march_cube`. A few configurations can't
+/// be triangulated without vertices inside the cube, which are passed as `CubeVertex::Interior`.
+pub fn march_cube_33<E>(values: &[f32; 8], iso_level: f32, mut vertex_func: E)
+where
+    E: FnMut(CubeVertex),
+{
+    let mut f = [0f32; 8];
+    for i in 0..8 {
+        f[i] = values[i] - iso_level;
+    }
+    let inside = |i: usize| f[i] <= 0.0;
+
+    // Corners joined along the surface of the cube, and later through its interior
+    let mut components = Components::new();
+    for edge in EDGE_CONNECTION.iter() {
+        if inside(edge[0]) == inside(edge[1]) {
+            components.union(edge[0], edge[1]);
+        }
+    }
+
+    // Each face contributes a segment of contour for every run of inside corners, running from
+    // the edge where the run ends to the edge where it starts (counter-clockwise), so that the
+    // inside is on the left when viewed from outside the cube. Each crossed edge therefore starts
+    // a segment on one face and ends a segment on the other.
+    let mut next = [NONE; 12];
+    for face in 0..6 {
+        let c = FACE_CORNERS[face];
+        let e = FACE_EDGES[face];
+        let ambiguous = inside(c[0]) == inside(c[2])
+            && inside(c[1]) == inside(c[3])
+            && inside(c[0]) != inside(c[1]);
+
+        if ambiguous {
+            // The asymptotic decider: the inside corners are joined across the face if the
+            // bilinear interpolant is inside the surface at its saddle point.
+            let p = if inside(c[0]) { 0 } else { 1 };
+            let joined = f[c[p]] * f[c[p + 2]] >= f[c[p + 1]] * f[c[(p + 3) % 4]];
+            if joined {
+                components.union(c[p], c[p + 2]);
+            } else {
+                components.union(c[p + 1], c[(p + 3) % 4]);
+            }
+
+            for i in 0..4 {
+                if inside(c[i]) && !joined {
+                    next[e[i]] = e[(i + 3) % 4];
+                } else if !inside(c[i]) && joined {
+                    next[e[(i + 3) % 4]] = e[i];
+                }
+            }
+        } else {
+            let mut start = NONE;
+            let mut end = NONE;
+            for i in 0..4 {
+                let j = (i + 1) % 4;
+                if inside(c[i]) && !inside(c[j]) {
+                    start = e[i];
+                } else if !inside(c[i]) && inside(c[j]) {
+                    end = e[i];
+                }
+            }
+            if start != NONE {
+                next[start] = end;
+            }
+        }
+    }
+
+    // Follow the segments around the cube to form loops. The edges of loop i are stored in
+    // edges[loops[i]..loops[i + 1]].
+    let mut edges = [0usize; 12];
+    let mut loops = [0usize; 5];
+    let mut loop_count = 0;
+    let mut edge_count = 0;
+    let mut visited = [false; 12];
+    for edge in 0..12 {
+        if next[edge] == NONE || visited[edge] {
+            continue;
+        }
+
+        let mut e = edge;
+        while !visited[e] {
+            visited[e] = true;
+            edges[edge_count] = e;
+            edge_count += 1;
+            e = next[e];
+        }
+        loop_count += 1;
+        loops[loop_count] = edge_count;
+    }
+
+    if loop_count > 1 {
+        connect_interior(&f, &mut components);
+    }
+
+    // Each piece of surface separates one region inside the surface from one region outside it,
+    // so loops bounding the same pair of regions belong to the same piece of surface.
+    let mut regions = [(0usize, 0usize); 4];
+    for l in 0..loop_count {
+        let edge = EDGE_CONNECTION[edges[loops[l]]];
+        let (a, b) = if inside(edge[0]) {
+            (edge[0], edge[1])
+        } else {
+            (edge[1], edge[0])
+        };
+        regions[l] = (components.find(a), components.find(b));
+    }
+
+    let mut interior = 0;
+    for l in 0..loop_count {
+        let mut partner = NONE;
+        let mut partner_count = 0;
+        for m in 0..loop_count {
+            if m != l && regions[m] == regions[l] {
+                partner = m;
+                partner_count += 1;
+            }
+        }
+
+        // The trilinear interpolant only ever produces discs, and tunnels joining two loops
+        let a = &edges[loops[l]..loops[l + 1]];
+        match partner_count {
+            1 if partner > l => {
+                let b = &edges[loops[partner]..loops[partner + 1]];
+                if !triangulate_tunnel(a, b, &f, &mut interior, &mut vertex_func) {
+                    // Capping both loops gives the wrong topology, but still closes the surface
+                    triangulate_disc(a, &f, &mut interior, &mut vertex_func);
+                    triangulate_disc(b, &f, &mut interior, &mut vertex_func);
+                }
+            }
+            1 => (),
+            _ => triangulate_disc(a, &f, &mut interior, &mut vertex_func),
+        }
+    }
+}
+
+// Marks an edge without a segment leading from it
+const NONE: usize = usize::MAX;
+
+// Find the corners joined through the interior of a cube.
+//
+// The cube is swept by planes perpendicular to the z axis. Each slice is a bilinear face whose
+// corners move linearly along the vertical edges of the cube, so the way its corners are joined
+// only changes where a vertical edge crosses the surface, or where the saddle point of the slice
+// crosses the surface. Sampling a slice between each of those events, and joining the corners
+// shared by consecutive slices, finds every path through the cube.
+fn connect_interior(f: &[f32; 8], components: &mut Components) {
+    let base = [f[0], f[1], f[2], f[3]];
+    let delta = [f[4] - f[0], f[5] - f[1], f[6] - f[2], f[7] - f[3]];
+
+    let mut events = [0f32; 6];
+    let mut event_count = 0;
+    {
+        let mut push = |t: f32| {
+            if t > 0.0 && t < 1.0 {
+                events[event_count] = t;
+                event_count += 1;
+            }
+        };
+
+        for k in 0..4 {
+            if (f[k] <= 0.0) != (f[k + 4] <= 0.0) {
+                push(f[k] / (f[k] - f[k + 4]));
+            }
+        }
+
+        // The decider for the slice at t is quadratic in t
+        let a = delta[0] * delta[2] - delta[1] * delta[3];
+        let b = base[0] * delta[2] + base[2] * delta[0] - base[1] * delta[3] - base[3] * delta[1];
+        let c = base[0] * base[2] - base[1] * base[3];
+        if a == 0.0 {
+            if b != 0.0 {
+                push(-c / b);
+            }
+        } else {
+            let discriminant = b * b - 4.0 * a * c;
+            if discriminant >= 0.0 {
+                let root = discriminant.sqrt();
+                push((-b - root) / (2.0 * a));
+                push((-b + root) / (2.0 * a));
+            }
+        }
+    }
+    let events = &mut events[..event_count];
+    events.sort_by(|a, b| a.total_cmp(b));
+
+    // Slices at the bottom and top faces, and between each pair of events
+    let slice_count = event_count + 3;
+    let mut previous = [false; 4];
+    for slice in 0..slice_count {
+        let mut g = [0f32; 4];
+        for k in 0..4 {
+            g[k] = if slice == 0 {
+                f[k]
+            } else if slice == slice_count - 1 {
+                f[k + 4]
+            } else {
+                let lower = if slice == 1 { 0.0 } else { events[slice - 2] };
+                let upper = if slice == slice_count - 2 {
+                    1.0
+                } else {
+                    events[slice - 1]
+                };
+                base[k] + delta[k] * (lower + upper) * 0.5
+            };
+        }
+
+        let node = |k: usize| 8 + slice * 4 + k;
+        let inside = [g[0] <= 0.0, g[1] <= 0.0, g[2] <= 0.0, g[3] <= 0.0];
+        for k in 0..4 {
+            if inside[k] == inside[(k + 1) % 4] {
+                components.union(node(k), node((k + 1) % 4));
+            }
+        }
+        if inside[0] == inside[2] && inside[1] == inside[3] && inside[0] != inside[1] {
+            let p = if inside[0] { 0 } else { 1 };
+            if g[p] * g[p + 2] >= g[p + 1] * g[(p + 3) % 4] {
+                components.union(node(p), node(p + 2));
+            } else {
+                components.union(node(p + 1), node((p + 3) % 4));
+            }
+        }
+
+        for k in 0..4 {
+            if slice == 0 {
+                components.union(node(k), k);
+            } else if inside[k] == previous[k] {
+                components.union(node(k), node(k) - 4);
+            }
+            if slice == slice_count - 1 {
+                components.union(node(k), k + 4);
+            }
+        }
+        previous = inside;
+    }
+}
+
+// Whether two edges lie on the same face of the cube. Joining their vertices would put a
+// triangle edge in the plane of that face, where it would overlap the neighbouring cube.
+fn share_face(a: usize, b: usize) -> bool {
+    FACE_EDGES
+        .iter()
+        .any(|face| face.contains(&a) && face.contains(&b))
+}
+
+// Whether two vertices may be joined by an edge inside the cube
+fn may_join(a: CubeVertex, b: CubeVertex) -> bool {
+    match (a, b) {
+        (CubeVertex::Edge(a), CubeVertex::Edge(b)) => !share_face(a, b),
+        _ => true,
+    }
+}
+
+// The position of a vertex relative to the cube, given the densities at its corners relative to
+// the iso-level
+fn position(vertex: CubeVertex, f: &[f32; 8]) -> Vec3 {
+    match vertex {
+        CubeVertex::Edge(edge) => {
+            let corner = |c: usize| {
+                Vec3::new(
+                    CORNERS[c][0] as f32,
+                    CORNERS[c][1] as f32,
+                    CORNERS[c][2] as f32,
+                )
+            };
+            let [u, v] = EDGE_CONNECTION[edge];
+            interpolate(corner(u), corner(v), get_offset(f[u], f[v], 0.0))
+        }
+        CubeVertex::Interior(_, position) => position,
+    }
+}
+
+// Triangulate a single loop without joining any two vertices on the same face.
+//
+// A fan suffices for most loops. Otherwise, a triangulation is found by dynamic programming over
+// the sub-polygons of the loop, and failing that, the loop is fanned around a new vertex at its
+// centroid.
+fn triangulate_disc<E>(edges: &[usize], f: &[f32; 8], interior: &mut usize, vertex_func: &mut E)
+where
+    E: FnMut(CubeVertex),
+{
+    let n = edges.len();
+    let vertex = |i: usize| CubeVertex::Edge(edges[i % n]);
+    let allowed = |i: usize, j: usize| j == i + 1 || !share_face(edges[i], edges[j]);
+
+    for apex in 0..n {
+        let fan = (2..n - 1).all(|k| {
+            let other = (apex + k) % n;
+            allowed(apex.min(other), apex.max(other))
+        });
+        if fan {
+            for k in 1..n - 1 {
+                vertex_func(vertex(apex));
+                vertex_func(vertex(apex + k));
+                vertex_func(vertex(apex + k + 1));
+            }
+            return;
+        }
+    }
+
+    // split[i][j] is the apex of the triangle standing on the chord from vertex i to vertex j
+    let mut split = [[NONE; 12]; 12];
+    for length in 2..n {
+        for i in 0..n - length {
+            let j = i + length;
+            if length < n - 1 && !allowed(i, j) {
+                continue;
+            }
+            split[i][j] = (i + 1..j)
+                .find(|&k| {
+                    (k == i + 1 || split[i][k] != NONE) && (j == k + 1 || split[k][j] != NONE)
+                })
+                .unwrap_or(NONE);
+        }
+    }
+
+    if split[0][n - 1] == NONE {
+        let mut centroid = Vec3::zero();
+        for i in 0..n {
+            centroid = centroid + position(vertex(i), f) * (1.0 / n as f32);
+        }
+        let centre = CubeVertex::Interior(*interior, centroid);
+        *interior += 1;
+
+        for i in 0..n {
+            vertex_func(centre);
+            vertex_func(vertex(i));
+            vertex_func(vertex(i + 1));
+        }
+        return;
+    }
+
+    let mut stack = [(0usize, 0usize); 12];
+    stack[0] = (0, n - 1);
+    let mut depth = 1;
+    while depth > 0 {
+        depth -= 1;
+        let (i, j) = stack[depth];
+        let k = split[i][j];
+        vertex_func(vertex(i));
+        vertex_func(vertex(k));
+        vertex_func(vertex(j));
+        if k > i + 1 {
+            stack[depth] = (i, k);
+            depth += 1;
+        }
+        if j > k + 1 {
+            stack[depth] = (k, j);
+            depth += 1;
+        }
+    }
+}
+
+// Triangulate a tunnel between two loops, which run in opposite directions around it.
+//
+// The loops are joined directly if possible. Otherwise a ring of new vertices is placed halfway
+// along the tunnel, parallel to the first loop, which can be joined to both loops. Returns false,
+// without producing any triangles, if the loops can't be joined either way.
+fn triangulate_tunnel<E>(
+    a: &[usize],
+    b: &[usize],
+    f: &[f32; 8],
+    interior: &mut usize,
+    vertex_func: &mut E,
+) -> bool
+where
+    E: FnMut(CubeVertex),
+{
+    let mut a_vertices = [CubeVertex::Edge(0); 12];
+    let mut b_vertices = [CubeVertex::Edge(0); 12];
+    for (v, &e) in a_vertices.iter_mut().zip(a) {
+        *v = CubeVertex::Edge(e);
+    }
+    for (v, &e) in b_vertices.iter_mut().zip(b) {
+        *v = CubeVertex::Edge(e);
+    }
+    let (a, b) = (&a_vertices[..a.len()], &b_vertices[..b.len()]);
+
+    if join_loops(a, b, f, vertex_func) {
+        return true;
+    }
+
+    let n = a.len();
+    let mut b_centroid = Vec3::zero();
+    for &v in b {
+        b_centroid = b_centroid + position(v, f) * (1.0 / b.len() as f32);
+    }
+    let mut ring = [CubeVertex::Edge(0); 12];
+    for i in 0..n {
+        let halfway = (position(a[i], f) + b_centroid) * 0.5;
+        ring[i] = CubeVertex::Interior(*interior + i, halfway);
+    }
+    let ring = &ring[..n];
+
+    // The ring runs in the same direction as the first loop, and may be joined to anything
+    if !join_loops(ring, b, f, vertex_func) {
+        return false;
+    }
+    *interior += n;
+
+    for i in 0..n {
+        let j = (i + 1) % n;
+        vertex_func(a[i]);
+        vertex_func(a[j]);
+        vertex_func(ring[i]);
+        vertex_func(ring[j]);
+        vertex_func(ring[i]);
+        vertex_func(a[j]);
+    }
+    true
+}
+
+// Join two loops running in opposite directions with a strip of triangles.
+//
+// The triangles advance along one loop or the other, each adding a rung between the loops. Rungs
+// may not join two vertices on the same face, so every starting rung is tried in order of length,
+// searching for a way around which prefers the shorter rung at each step. Returns false if there
+// is no way around.
+fn join_loops<E>(a: &[CubeVertex], b: &[CubeVertex], f: &[f32; 8], vertex_func: &mut E) -> bool
+where
+    E: FnMut(CubeVertex),
+{
+    let length = |u: CubeVertex, v: CubeVertex| (position(u, f) - position(v, f)).length();
+
+    let (n, m) = (a.len(), b.len());
+    let mut starts = Vec::with_capacity(n * m);
+    for (i, &u) in a.iter().enumerate() {
+        for (j, &v) in b.iter().enumerate() {
+            if may_join(u, v) {
+                starts.push((length(u, v), i, j));
+            }
+        }
+    }
+    starts.sort_by(|x, y| x.0.total_cmp(&y.0));
+
+    for &(_, si, sj) in &starts {
+        // The vertices after i steps along a, and j steps (backwards) along b
+        let va = |i: usize| a[(si + i) % n];
+        let vb = |j: usize| b[(sj + m - j % m) % m];
+        let rung = |i: usize, j: usize| {
+            if may_join(va(i), vb(j)) {
+                Some(length(va(i), vb(j)))
+            } else {
+                None
+            }
+        };
+
+        // reachable[i][j] is whether the rung after i and j steps can reach the end. Search
+        // backwards from the end, so that the way forward can be read off directly. The first step
+        // must be along a, and the last along b, or the starting rung would be used twice.
+        let mut reachable = [[false; 13]; 13];
+        reachable[n][m] = true;
+        for i in (0..n + 1).rev() {
+            for j in (0..m).rev() {
+                if rung(i, j).is_none() || (i, j) == (n, 0) {
+                    continue;
+                }
+                reachable[i][j] =
+                    (i < n && reachable[i + 1][j]) || ((i, j) != (0, 0) && reachable[i][j + 1]);
+            }
+        }
+        if !reachable[0][0] {
+            continue;
+        }
+
+        let (mut i, mut j) = (0, 0);
+        while (i, j) != (n, m) {
+            let along_a = i < n && reachable[i + 1][j];
+            let along_b = (i, j) != (0, 0) && j < m && reachable[i][j + 1];
+            if along_a && (!along_b || rung(i + 1, j) <= rung(i, j + 1)) {
+                vertex_func(va(i));
+                vertex_func(va(i + 1));
+                vertex_func(vb(j));
+                i += 1;
+            } else {
+                vertex_func(vb(j + 1));
+                vertex_func(vb(j));
+                vertex_func(va(i));
+                j += 1;
+            }
+        }
+        return true;
+    }
+    false
+}
+
+// A union-find over the 8 corners of a cube, followed by the corners of each slice through it
+struct Components {
+    parent: [u8; 48],
+}
+
+impl Components {
+    fn new() -> Components {
+        let mut parent = [0u8; 48];
+        for (i, p) in parent.iter_mut().enumerate() {
+            *p = i as u8;
+        }
+        Components { parent }
+    }
+
+    fn find(&mut self, mut i: usize) -> usize {
+        while self.parent[i] as usize != i {
+            self.parent[i] = self.parent[self.parent[i] as usize];
+            i = self.parent[i] as usize;
+        }
+        i
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let a = self.find(a);
+        let b = self.find(b);
+        self.parent[a] = b as u8;
+    }
+}
+
+/// Calculate the position of the vertex along an edge, given the density at either end of the edge,
+/// and the iso-level at which to extract the surface.
+pub fn get_offset(a: f32, b: f32, iso_level: f32) -> f32 {
     let delta = b - a;
     if delta == 0.0 {
         0.5
     } else {
-        -a / delta
+        (iso_level - a) / delta
     }
 }
 
@@ -84,3 +646,54 @@ where
 {
     a * (1.0 - t) + b * t
 }
+
+/// Find the vertex along an edge, given the corners at either end of the edge, their densities, and
+/// the iso-level at which to extract the surface.
+///
+/// The result does not depend on which way round the edge is given, so that every cell sharing an
+/// edge produces a bit-identical vertex.
+pub fn edge_vertex(a: Vec3, b: Vec3, value_a: f32, value_b: f32, iso_level: f32) -> Vec3 {
+    if b < a {
+        interpolate(b, a, get_offset(value_b, value_a, iso_level))
+    } else {
+        interpolate(a, b, get_offset(value_a, value_b, iso_level))
+    }
+}
+
+/// Gathers the vertex indices produced by `march_cube` into triangles
+pub struct Triangle {
+    indices: [u32; 3],
+    count: usize,
+}
+
+impl Triangle {
+    /// Create an empty triangle
+    pub fn new() -> Triangle {
+        Triangle {
+            indices: [0; 3],
+            count: 0,
+        }
+    }
+
+    /// Add the next vertex index, passing the triangle to the `sink` once it is complete
+    pub fn push<M>(&mut self, index: u32, sink: &mut M)
+    where
+        M: MeshSink,
+    {
+        if let Some([a, b, c]) = self.next(index) {
+            sink.add_triangle(a, b, c);
+        }
+    }
+
+    /// Add the next vertex index, returning the triangle once it is complete
+    pub fn next(&mut self, index: u32) -> Option<[u32; 3]> {
+        self.indices[self.count] = index;
+        self.count += 1;
+        if self.count == 3 {
+            self.count = 0;
+            Some(self.indices)
+        } else {
+            None
+        }
+    }
+}
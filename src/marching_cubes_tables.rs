@@ -40,6 +40,27 @@ pub const EDGE_CONNECTION: [[usize; 2]; 12] = [
     [3, 7],
 ];
 
+/// The corners of each face of a cell, counter-clockwise when viewed from outside the cell
+pub const FACE_CORNERS: [[usize; 4]; 6] = [
+    [0, 3, 2, 1],
+    [4, 5, 6, 7],
+    [0, 1, 5, 4],
+    [3, 7, 6, 2],
+    [0, 4, 7, 3],
+    [1, 2, 6, 5],
+];
+
+/// The edges of each face of a cell, where edge `i` runs from corner `i` to corner `i + 1` of the
+/// face in `FACE_CORNERS`
+pub const FACE_EDGES: [[usize; 4]; 6] = [
+    [3, 2, 1, 0],
+    [4, 5, 6, 7],
+    [0, 9, 4, 8],
+    [11, 6, 10, 2],
+    [8, 7, 11, 3],
+    [1, 10, 5, 9],
+];
+
 /// Maps the signs of each corner in a cell to the set of triangles spanning the active edges
 pub const TRIANGLE_CONNECTION: [[i8; 16]; 256] = [
     [
@@ -0,0 +1,59 @@
+// Copyright 2018 Tristam MacDonald
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// The corners of the 6 tetrahedra which fill a cell, as indices into `CORNERS`.
+///
+/// Every tetrahedron spans the diagonal from corner 0 to corner 6, and the corners of each are
+/// ordered so that all 6 share the same orientation. Each face of the cell is split along the
+/// diagonal from its lowest to its highest corner, so neighbouring cells always agree on how their
+/// shared face is split.
+pub const TETRAHEDRA: [[usize; 4]; 6] = [
+    [0, 1, 2, 6],
+    [0, 1, 6, 5],
+    [0, 3, 6, 2],
+    [0, 3, 7, 6],
+    [0, 4, 5, 6],
+    [0, 4, 6, 7],
+];
+
+/// The corners used by each edge in a tetrahedron
+pub const TETRAHEDRON_EDGES: [[usize; 2]; 6] = [
+    [0, 1],
+    [0, 2],
+    [0, 3],
+    [1, 2],
+    [1, 3],
+    [2, 3],
+];
+
+/// Maps the signs of each corner in a tetrahedron to the set of triangles spanning the active
+/// edges
+pub const TETRAHEDRON_TRIANGLES: [[i8; 7]; 16] = [
+    [-1, -1, -1, -1, -1, -1, -1],
+    [0, 2, 1, -1, -1, -1, -1],
+    [0, 3, 4, -1, -1, -1, -1],
+    [1, 4, 2, 1, 3, 4, -1],
+    [1, 5, 3, -1, -1, -1, -1],
+    [0, 2, 5, 0, 5, 3, -1],
+    [0, 1, 5, 0, 5, 4, -1],
+    [2, 5, 4, -1, -1, -1, -1],
+    [2, 4, 5, -1, -1, -1, -1],
+    [0, 5, 1, 0, 4, 5, -1],
+    [0, 5, 2, 0, 3, 5, -1],
+    [1, 3, 5, -1, -1, -1, -1],
+    [1, 2, 4, 1, 4, 3, -1],
+    [0, 4, 3, -1, -1, -1, -1],
+    [0, 1, 2, -1, -1, -1, -1],
+    [-1, -1, -1, -1, -1, -1, -1],
+];
@@ -48,6 +48,35 @@ impl Vec3 {
             z: 1.0,
         }
     }
+
+    /// The dot product of two vectors
+    pub fn dot(&self, other: Vec3) -> f32 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    /// The cross product of two vectors
+    pub fn cross(&self, other: Vec3) -> Vec3 {
+        Vec3::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    /// The length of the vector
+    pub fn length(&self) -> f32 {
+        self.dot(*self).sqrt()
+    }
+
+    /// Scale the vector to unit length. Vectors of zero length are returned unchanged.
+    pub fn normalize(&self) -> Vec3 {
+        let length = self.length();
+        if length > 0.0 {
+            *self * (1.0 / length)
+        } else {
+            *self
+        }
+    }
 }
 
 impl std::ops::Add for Vec3 {
@@ -89,3 +118,427 @@ impl std::ops::Div for Vec3 {
         Vec3::new(self.x / other.x, self.y / other.y, self.z / other.z)
     }
 }
+
+impl std::ops::Index<usize> for Vec3 {
+    type Output = f32;
+
+    fn index(&self, axis: usize) -> &f32 {
+        match axis {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            _ => panic!("Vec3 axis out of range: {}", axis),
+        }
+    }
+}
+
+impl std::ops::IndexMut<usize> for Vec3 {
+    fn index_mut(&mut self, axis: usize) -> &mut f32 {
+        match axis {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            2 => &mut self.z,
+            _ => panic!("Vec3 axis out of range: {}", axis),
+        }
+    }
+}
+
+/// A quaternion, used to represent rotations in 3 dimensions
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Quat {
+    /// The x component of the vector part
+    pub x: f32,
+    /// The y component of the vector part
+    pub y: f32,
+    /// The z component of the vector part
+    pub z: f32,
+    /// The scalar part
+    pub w: f32,
+}
+
+impl Quat {
+    /// Create a quaternion
+    pub fn new(x: f32, y: f32, z: f32, w: f32) -> Self {
+        Self { x, y, z, w }
+    }
+
+    /// Create a quaternion representing no rotation
+    pub fn identity() -> Self {
+        Self::new(0.0, 0.0, 0.0, 1.0)
+    }
+
+    /// Create a quaternion representing a rotation by `angle` radians around `axis`
+    pub fn from_axis_angle(axis: Vec3, angle: f32) -> Self {
+        let axis = axis.normalize() * (angle * 0.5).sin();
+        Self::new(axis.x, axis.y, axis.z, (angle * 0.5).cos())
+    }
+
+    /// The conjugate of the quaternion, which represents the inverse rotation of a unit
+    /// quaternion
+    pub fn conjugate(&self) -> Quat {
+        Quat::new(-self.x, -self.y, -self.z, self.w)
+    }
+
+    /// Scale the quaternion to unit length. Quaternions of zero length are returned unchanged.
+    pub fn normalize(&self) -> Quat {
+        let length = (self.x * self.x + self.y * self.y + self.z * self.z + self.w * self.w).sqrt();
+        if length > 0.0 {
+            Quat::new(
+                self.x / length,
+                self.y / length,
+                self.z / length,
+                self.w / length,
+            )
+        } else {
+            *self
+        }
+    }
+
+    /// Rotate a vector by this (unit) quaternion
+    pub fn rotate(&self, v: Vec3) -> Vec3 {
+        let q = Vec3::new(self.x, self.y, self.z);
+        let t = q.cross(v) * 2.0;
+        v + t * self.w + q.cross(t)
+    }
+}
+
+impl std::ops::Mul for Quat {
+    type Output = Quat;
+
+    fn mul(self, other: Quat) -> Quat {
+        Quat::new(
+            self.w * other.x + self.x * other.w + self.y * other.z - self.z * other.y,
+            self.w * other.y - self.x * other.z + self.y * other.w + self.z * other.x,
+            self.w * other.z + self.x * other.y - self.y * other.x + self.z * other.w,
+            self.w * other.w - self.x * other.x - self.y * other.y - self.z * other.z,
+        )
+    }
+}
+
+/// A closed range of values, used to bound the values of a source over a region of space
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Interval {
+    /// The lowest value in the range
+    pub min: f32,
+    /// The highest value in the range
+    pub max: f32,
+}
+
+impl Interval {
+    /// Create an interval
+    pub fn new(min: f32, max: f32) -> Self {
+        Self { min, max }
+    }
+
+    /// Whether the interval contains the given value
+    pub fn contains(&self, value: f32) -> bool {
+        self.min <= value && value <= self.max
+    }
+
+    /// The interval containing the minimum of any pair of values from two intervals
+    pub fn min(self, other: Interval) -> Interval {
+        Interval::new(self.min.min(other.min), self.max.min(other.max))
+    }
+
+    /// The interval containing the maximum of any pair of values from two intervals
+    pub fn max(self, other: Interval) -> Interval {
+        Interval::new(self.min.max(other.min), self.max.max(other.max))
+    }
+}
+
+impl std::ops::Neg for Interval {
+    type Output = Interval;
+
+    fn neg(self) -> Interval {
+        Interval::new(-self.max, -self.min)
+    }
+}
+
+impl std::ops::Add<f32> for Interval {
+    type Output = Interval;
+
+    fn add(self, other: f32) -> Interval {
+        Interval::new(self.min + other, self.max + other)
+    }
+}
+
+impl std::ops::Sub<f32> for Interval {
+    type Output = Interval;
+
+    fn sub(self, other: f32) -> Interval {
+        Interval::new(self.min - other, self.max - other)
+    }
+}
+
+impl std::ops::Mul<f32> for Interval {
+    type Output = Interval;
+
+    fn mul(self, other: f32) -> Interval {
+        if other < 0.0 {
+            Interval::new(self.max * other, self.min * other)
+        } else {
+            Interval::new(self.min * other, self.max * other)
+        }
+    }
+}
+
+/// The arithmetic needed to evaluate a [`Field`](../source/trait.Field.html).
+///
+/// Implemented for `f32`, to evaluate just the value of a field, and for [`Dual`](struct.Dual.html),
+/// to evaluate its gradient alongside. Comparisons between scalars should be made on their
+/// [`value`](#tymethod.value), which is the same either way.
+pub trait Scalar:
+    Copy
+    + std::ops::Add<Output = Self>
+    + std::ops::Sub<Output = Self>
+    + std::ops::Mul<Output = Self>
+    + std::ops::Div<Output = Self>
+    + std::ops::Neg<Output = Self>
+    + std::ops::Add<f32, Output = Self>
+    + std::ops::Sub<f32, Output = Self>
+    + std::ops::Mul<f32, Output = Self>
+    + std::ops::Div<f32, Output = Self>
+{
+    /// A scalar which doesn't vary with position
+    fn constant(value: f32) -> Self;
+
+    /// The value of the scalar, without any derivatives
+    fn value(self) -> f32;
+
+    /// The square root
+    fn sqrt(self) -> Self;
+
+    /// The absolute value
+    fn abs(self) -> Self;
+
+    /// The lesser of two scalars
+    fn min(self, other: Self) -> Self;
+
+    /// The greater of two scalars
+    fn max(self, other: Self) -> Self;
+
+    /// The exponential function, e to the power of the scalar
+    fn exp(self) -> Self;
+
+    /// The natural logarithm of one plus the scalar
+    fn ln_1p(self) -> Self;
+
+    /// The sine, in radians
+    fn sin(self) -> Self;
+
+    /// The cosine, in radians
+    fn cos(self) -> Self;
+}
+
+impl Scalar for f32 {
+    fn constant(value: f32) -> f32 {
+        value
+    }
+
+    fn value(self) -> f32 {
+        self
+    }
+
+    fn sqrt(self) -> f32 {
+        f32::sqrt(self)
+    }
+
+    fn abs(self) -> f32 {
+        f32::abs(self)
+    }
+
+    fn min(self, other: f32) -> f32 {
+        f32::min(self, other)
+    }
+
+    fn max(self, other: f32) -> f32 {
+        f32::max(self, other)
+    }
+
+    fn exp(self) -> f32 {
+        f32::exp(self)
+    }
+
+    fn ln_1p(self) -> f32 {
+        f32::ln_1p(self)
+    }
+
+    fn sin(self) -> f32 {
+        f32::sin(self)
+    }
+
+    fn cos(self) -> f32 {
+        f32::cos(self)
+    }
+}
+
+/// A dual number, which carries the gradient of a value with respect to position along with it
+///
+/// Evaluating a [`Field`](../source/trait.Field.html) with dual numbers applies the chain rule at
+/// every step, so the result holds the exact gradient of the field, from a single evaluation.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Dual {
+    /// The value of the function
+    pub value: f32,
+    /// The partial derivatives of the value along x, y and z
+    pub gradient: Vec3,
+}
+
+impl Dual {
+    /// Create a dual number
+    pub fn new(value: f32, gradient: Vec3) -> Self {
+        Self { value, gradient }
+    }
+
+    /// Create dual numbers for each coordinate of a position, to evaluate a field at
+    pub fn position(x: f32, y: f32, z: f32) -> (Dual, Dual, Dual) {
+        (
+            Dual::new(x, Vec3::new(1.0, 0.0, 0.0)),
+            Dual::new(y, Vec3::new(0.0, 1.0, 0.0)),
+            Dual::new(z, Vec3::new(0.0, 0.0, 1.0)),
+        )
+    }
+}
+
+impl Scalar for Dual {
+    fn constant(value: f32) -> Dual {
+        Dual::new(value, Vec3::zero())
+    }
+
+    fn value(self) -> f32 {
+        self.value
+    }
+
+    fn sqrt(self) -> Dual {
+        let value = self.value.sqrt();
+        // The derivative is infinite at zero, where the gradient of a distance field is undefined
+        if value > 0.0 {
+            Dual::new(value, self.gradient * (0.5 / value))
+        } else {
+            Dual::new(value, Vec3::zero())
+        }
+    }
+
+    fn abs(self) -> Dual {
+        if self.value < 0.0 {
+            -self
+        } else {
+            self
+        }
+    }
+
+    fn min(self, other: Dual) -> Dual {
+        if other.value < self.value {
+            other
+        } else {
+            self
+        }
+    }
+
+    fn max(self, other: Dual) -> Dual {
+        if other.value > self.value {
+            other
+        } else {
+            self
+        }
+    }
+
+    fn exp(self) -> Dual {
+        let value = self.value.exp();
+        Dual::new(value, self.gradient * value)
+    }
+
+    fn ln_1p(self) -> Dual {
+        Dual::new(
+            self.value.ln_1p(),
+            self.gradient * (1.0 / (1.0 + self.value)),
+        )
+    }
+
+    fn sin(self) -> Dual {
+        Dual::new(self.value.sin(), self.gradient * self.value.cos())
+    }
+
+    fn cos(self) -> Dual {
+        Dual::new(self.value.cos(), self.gradient * -self.value.sin())
+    }
+}
+
+impl std::ops::Add for Dual {
+    type Output = Dual;
+
+    fn add(self, other: Dual) -> Dual {
+        Dual::new(self.value + other.value, self.gradient + other.gradient)
+    }
+}
+
+impl std::ops::Sub for Dual {
+    type Output = Dual;
+
+    fn sub(self, other: Dual) -> Dual {
+        Dual::new(self.value - other.value, self.gradient - other.gradient)
+    }
+}
+
+impl std::ops::Mul for Dual {
+    type Output = Dual;
+
+    fn mul(self, other: Dual) -> Dual {
+        Dual::new(
+            self.value * other.value,
+            self.gradient * other.value + other.gradient * self.value,
+        )
+    }
+}
+
+impl std::ops::Div for Dual {
+    type Output = Dual;
+
+    fn div(self, other: Dual) -> Dual {
+        let inverse = 1.0 / other.value;
+        Dual::new(
+            self.value * inverse,
+            (self.gradient - other.gradient * (self.value * inverse)) * inverse,
+        )
+    }
+}
+
+impl std::ops::Neg for Dual {
+    type Output = Dual;
+
+    fn neg(self) -> Dual {
+        Dual::new(-self.value, self.gradient * -1.0)
+    }
+}
+
+impl std::ops::Add<f32> for Dual {
+    type Output = Dual;
+
+    fn add(self, other: f32) -> Dual {
+        Dual::new(self.value + other, self.gradient)
+    }
+}
+
+impl std::ops::Sub<f32> for Dual {
+    type Output = Dual;
+
+    fn sub(self, other: f32) -> Dual {
+        Dual::new(self.value - other, self.gradient)
+    }
+}
+
+impl std::ops::Mul<f32> for Dual {
+    type Output = Dual;
+
+    fn mul(self, other: f32) -> Dual {
+        Dual::new(self.value * other, self.gradient * other)
+    }
+}
+
+impl std::ops::Div<f32> for Dual {
+    type Output = Dual;
+
+    fn div(self, other: f32) -> Dual {
+        Dual::new(self.value / other, self.gradient * (1.0 / other))
+    }
+}
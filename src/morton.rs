@@ -33,10 +33,21 @@ const DILATE_TX: u64 = 0x9249_2492_4924_9249; // 0,3,6,9,12
 const DILATE_T1: u64 = 0xB6DB_6DB6_DB6D_B6DB; // ~tz
 const DILATE_T2: u64 = 0xDB6D_B6DB_6DB6_DB6D; // ~ty
 const DILATE_T3: u64 = 0x6DB6_DB6D_B6DB_6DB6; // ~tx
-const LG2_3: f64 = 0.480_898_346_96; // 1.0 / (ln(2) * 3);
-const MAX_LEVEL: usize = (8 * 8 - 1) / 3; // ((sizeof(u64) in bits) - 1) / 3
-
-/// Refer to an octree node via interleaved integer coordinates
+const DILATE_32: u64 = 0x001F_0000_0000_FFFF;
+const DILATE_16: u64 = 0x001F_0000_FF00_00FF;
+const DILATE_8: u64 = 0x100F_00F0_0F00_F00F;
+const DILATE_4: u64 = 0x10C3_0C30_C30C_30C3;
+const DILATE_2: u64 = 0x1249_2492_4924_9249;
+
+/// The deepest level an octree node can be addressed at
+pub const MAX_LEVEL: usize = (8 * 8 - 1) / 3; // ((sizeof(u64) in bits) - 1) / 3
+
+/// Refer to an octree node via interleaved integer coordinates.
+///
+/// The root node spans the unit cube from (0,0,0) to (1,1,1), and each level halves the size of
+/// the nodes along every axis. A node at a given level is identified by its integer coordinates
+/// within the `2.pow(level)` nodes along each axis, which are interleaved bit by bit behind a
+/// leading bit marking the level.
 #[derive(Default, Hash, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
 pub struct Morton(u64);
 
@@ -51,11 +62,46 @@ impl Morton {
         Morton(key)
     }
 
+    /// Creates a morton code that points to the node at the given integer coordinates and level,
+    /// or `None` if the level is deeper than [`MAX_LEVEL`](constant.MAX_LEVEL.html), or the
+    /// coordinates lie outside the root node.
+    pub fn from_coordinates(x: u32, y: u32, z: u32, level: usize) -> Option<Self> {
+        if level > MAX_LEVEL || [x, y, z].iter().any(|&c| u64::from(c) >= 1 << level) {
+            return None;
+        }
+
+        Some(Morton(
+            (1 << (3 * level)) | dilate(x) | (dilate(y) << 1) | (dilate(z) << 2),
+        ))
+    }
+
+    /// Creates a morton code that points to the node at the given level which contains the given
+    /// point, or `None` if the point lies outside the root node.
+    pub fn from_point(point: Vec3, level: usize) -> Option<Self> {
+        let scale = (1u64 << level.min(MAX_LEVEL)) as f32;
+        let mut coordinates = [0; 3];
+        for axis in 0..3 {
+            if !(point[axis] >= 0.0 && point[axis] <= 1.0) {
+                return None;
+            }
+            // Points on the far faces of the root belong to the last node along that axis
+            coordinates[axis] = ((point[axis] * scale) as u32).min(scale as u32 - 1);
+        }
+
+        Self::from_coordinates(coordinates[0], coordinates[1], coordinates[2], level)
+    }
+
+    /// The key code of this morton code
+    pub fn key(&self) -> u64 {
+        self.0
+    }
+
     /// The depth of this octree node
     pub fn level(&self) -> usize {
+        // The level is given by the position of the leading bit, which marks the root node
         match self.0 {
             0 => 0,
-            a => ((a as f64).ln() * LG2_3).floor() as usize,
+            a => (63 - a.leading_zeros() as usize) / 3,
         }
     }
 
@@ -65,7 +111,11 @@ impl Morton {
     }
 
     /// Get one of the 8 child nodes of this octree node.
+    ///
+    /// Bits 0, 1 and 2 of `which` select the upper half of the node along the x, y and z axes
+    /// respectively.
     pub fn child(&self, which: u8) -> Self {
+        debug_assert!(which < 8);
         Morton((self.0 << 3) | u64::from(which))
     }
 
@@ -74,8 +124,8 @@ impl Morton {
         1.0 / ((2 << self.level()) as f32)
     }
 
-    /// Get the center of this octree node as a vector.
-    pub fn center(&self) -> Vec3 {
+    /// The integer coordinates of this octree node within its level.
+    pub fn coordinates(&self) -> [u32; 3] {
         let mut bz = (self.0 >> 2) & DILATE_MASK_0;
         let mut by = (self.0 >> 1) & DILATE_MASK_0;
         let mut bx = self.0 & DILATE_MASK_0;
@@ -101,9 +151,16 @@ impl Morton {
         }
 
         let length_mask = (1 << level) - 1;
-        bz &= length_mask;
-        by &= length_mask;
-        bx &= length_mask;
+        [
+            (bx & length_mask) as u32,
+            (by & length_mask) as u32,
+            (bz & length_mask) as u32,
+        ]
+    }
+
+    /// Get the center of this octree node as a vector.
+    pub fn center(&self) -> Vec3 {
+        let [bx, by, bz] = self.coordinates();
 
         let size = self.size();
         let size2 = size * 2.0;
@@ -115,13 +172,30 @@ impl Morton {
         )
     }
 
+    /// The node at the same level as this one, offset by the given number of nodes along each
+    /// axis, or `None` if that would lie outside the root node.
+    pub fn neighbour(&self, offset: [i32; 3]) -> Option<Self> {
+        let level = self.level();
+        let coordinates = self.coordinates();
+        let mut neighbour = [0; 3];
+        for axis in 0..3 {
+            let c = i64::from(coordinates[axis]) + i64::from(offset[axis]);
+            if c < 0 || c >= 1 << level {
+                return None;
+            }
+            neighbour[axis] = c as u32;
+        }
+
+        Self::from_coordinates(neighbour[0], neighbour[1], neighbour[2], level)
+    }
+
     /// Assuming that self is a point on the dual mesh, finds the 8 corresponding vertices on the primal mesh.
-    pub fn primal_vertex(&self, level: usize, which: usize) -> Morton {
+    pub(crate) fn primal_vertex(&self, level: usize, which: usize) -> Morton {
         let k = 1 << (3 * level);
         let k_plus_one = k << 1;
 
-        let vk = *self + Morton(which as u64);
-        let dk = (vk - Morton(k)).0;
+        let vk = add(*self, Morton(which as u64));
+        let dk = sub(vk, Morton(k)).0;
 
         if vk.0 >= k_plus_one || (dk & DILATE_TX) == 0 || (dk & DILATE_TY) == 0
             || (dk & DILATE_TZ) == 0
@@ -133,35 +207,39 @@ impl Morton {
     }
 
     /// Assuming that self is a point on the primal mesh, finds the 8 corresponding vertices on the dual mesh.
-    pub fn dual_vertex(&self, level: usize, which: usize) -> Morton {
+    pub(crate) fn dual_vertex(&self, level: usize, which: usize) -> Morton {
         let dk = Morton(self.0 >> (3 * (MAX_LEVEL - level)));
 
-        dk - Morton(which as u64)
+        sub(dk, Morton(which as u64))
     }
 }
 
-impl std::ops::Add for Morton {
-    type Output = Morton;
-
-    fn add(self, other: Morton) -> Morton {
-        Morton(
-            (((self.0 | DILATE_T1) + (other.0 & DILATE_TZ)) & DILATE_TZ)
-                | (((self.0 | DILATE_T2) + (other.0 & DILATE_TY)) & DILATE_TY)
-                | (((self.0 | DILATE_T3) + (other.0 & DILATE_TX)) & DILATE_TX),
-        )
-    }
+// Spread the bits of an integer coordinate out to every third bit
+fn dilate(coordinate: u32) -> u64 {
+    let mut d = u64::from(coordinate) & ((1 << MAX_LEVEL) - 1);
+    d = (d | (d << 32)) & DILATE_32;
+    d = (d | (d << 16)) & DILATE_16;
+    d = (d | (d << 8)) & DILATE_8;
+    d = (d | (d << 4)) & DILATE_4;
+    (d | (d << 2)) & DILATE_2
 }
 
-impl std::ops::Sub for Morton {
-    type Output = Morton;
+// Add the interleaved coordinates of two morton codes, axis by axis
+fn add(a: Morton, b: Morton) -> Morton {
+    Morton(
+        (((a.0 | DILATE_T1) + (b.0 & DILATE_TZ)) & DILATE_TZ)
+            | (((a.0 | DILATE_T2) + (b.0 & DILATE_TY)) & DILATE_TY)
+            | (((a.0 | DILATE_T3) + (b.0 & DILATE_TX)) & DILATE_TX),
+    )
+}
 
-    fn sub(self, other: Morton) -> Morton {
-        Morton(
-            (((self.0 & DILATE_TZ).wrapping_sub(other.0 & DILATE_TZ)) & DILATE_TZ)
-                | (((self.0 & DILATE_TY).wrapping_sub(other.0 & DILATE_TY)) & DILATE_TY)
-                | (((self.0 & DILATE_TX).wrapping_sub(other.0 & DILATE_TX)) & DILATE_TX),
-        )
-    }
+// Subtract the interleaved coordinates of one morton code from another, axis by axis
+fn sub(a: Morton, b: Morton) -> Morton {
+    Morton(
+        (((a.0 & DILATE_TZ).wrapping_sub(b.0 & DILATE_TZ)) & DILATE_TZ)
+            | (((a.0 & DILATE_TY).wrapping_sub(b.0 & DILATE_TY)) & DILATE_TY)
+            | (((a.0 & DILATE_TX).wrapping_sub(b.0 & DILATE_TX)) & DILATE_TX),
+    )
 }
 
 impl std::convert::From<Morton> for usize {
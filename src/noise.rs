@@ -0,0 +1,605 @@
+// Copyright 2018 Tristam MacDonald
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use math::Vec3;
+use source::{HermiteSource, Source};
+
+// The 12 edges of a cube, padded to 16 so that they can be selected by the low bits of a hash
+const GRADIENTS: [[f32; 3]; 16] = [
+    [1.0, 1.0, 0.0],
+    [-1.0, 1.0, 0.0],
+    [1.0, -1.0, 0.0],
+    [-1.0, -1.0, 0.0],
+    [1.0, 0.0, 1.0],
+    [-1.0, 0.0, 1.0],
+    [1.0, 0.0, -1.0],
+    [-1.0, 0.0, -1.0],
+    [0.0, 1.0, 1.0],
+    [0.0, -1.0, 1.0],
+    [0.0, 1.0, -1.0],
+    [0.0, -1.0, -1.0],
+    [1.0, 1.0, 0.0],
+    [0.0, -1.0, 1.0],
+    [-1.0, 1.0, 0.0],
+    [0.0, -1.0, -1.0],
+];
+
+// Successive octaves are shifted by this much, so that their lattices don't all line up at the
+// origin
+const OCTAVE_SHIFT: [f32; 3] = [19.19, 33.87, 47.43];
+
+// Factors to skew the input space onto the simplex grid, and to unskew it again
+const F3: f32 = 1.0 / 3.0;
+const G3: f32 = 1.0 / 6.0;
+
+// Scales simplex noise to roughly the same range as gradient noise
+const SIMPLEX_SCALE: f32 = 76.0;
+
+/// A source which evaluates its analytic gradient along with its value.
+///
+/// Every noise in this module implements Noise, and the fractal adaptors carry the gradients of
+/// the noise they are built from through the chain rule, so their normals are exact, and don't
+/// need to be estimated with [CentralDifference](../source/struct.CentralDifference.html).
+pub trait Noise {
+    /// Samples the noise at the given (x, y, z) coordinates, returning its value and gradient.
+    fn sample_with_gradient(&self, x: f32, y: f32, z: f32) -> (f32, Vec3);
+}
+
+impl<N: Noise + ?Sized> Noise for &N {
+    fn sample_with_gradient(&self, x: f32, y: f32, z: f32) -> (f32, Vec3) {
+        (**self).sample_with_gradient(x, y, z)
+    }
+}
+
+impl<N: Noise + ?Sized> Noise for Box<N> {
+    fn sample_with_gradient(&self, x: f32, y: f32, z: f32) -> (f32, Vec3) {
+        (**self).sample_with_gradient(x, y, z)
+    }
+}
+
+/// Classic gradient noise, as per Ken Perlin's [Improving Noise](https://mrl.cs.nyu.edu/~perlin/paper445.pdf).
+///
+/// Values lie roughly within -1 to 1, and are zero at every integer lattice point.
+#[derive(Debug, Clone)]
+pub struct Perlin {
+    permutation: Permutation,
+}
+
+impl Perlin {
+    /// Create gradient noise, which is the same for every run with the same seed
+    pub fn new(seed: u32) -> Self {
+        Self {
+            permutation: Permutation::new(seed),
+        }
+    }
+}
+
+impl Noise for Perlin {
+    fn sample_with_gradient(&self, x: f32, y: f32, z: f32) -> (f32, Vec3) {
+        let (cell, f) = lattice_cell(x, y, z);
+        let mut values = [0.0; 8];
+        let mut gradients = [Vec3::zero(); 8];
+        for corner in 0..8 {
+            let offset = corner_offset(corner);
+            let g = gradient(self.permutation.hash(cell, offset));
+            values[corner] = g.dot(f - offset_vector(offset));
+            gradients[corner] = g;
+        }
+
+        interpolate(f, &values, &gradients)
+    }
+}
+
+/// Ken Perlin's simplex noise, which interpolates gradients over a grid of tetrahedra rather
+/// than cubes.
+///
+/// Values lie roughly within -1 to 1. Compared to [Perlin](struct.Perlin.html) noise, it has
+/// fewer directional artifacts, and is cheaper to evaluate.
+#[derive(Debug, Clone)]
+pub struct Simplex {
+    permutation: Permutation,
+}
+
+impl Simplex {
+    /// Create simplex noise, which is the same for every run with the same seed
+    pub fn new(seed: u32) -> Self {
+        Self {
+            permutation: Permutation::new(seed),
+        }
+    }
+}
+
+impl Noise for Simplex {
+    fn sample_with_gradient(&self, x: f32, y: f32, z: f32) -> (f32, Vec3) {
+        // Skew the input space to find the cell of the simplex grid containing the point
+        let s = (x + y + z) * F3;
+        let cell = [
+            (x + s).floor() as i32,
+            (y + s).floor() as i32,
+            (z + s).floor() as i32,
+        ];
+        let t = (cell[0] + cell[1] + cell[2]) as f32 * G3;
+        let p = Vec3::new(x, y, z) - offset_vector(cell) + Vec3::one() * t;
+
+        // Rank the coordinates within the cell to find which of its 6 tetrahedra holds the point
+        let (second, third) = if p.x >= p.y {
+            if p.y >= p.z {
+                ([1, 0, 0], [1, 1, 0])
+            } else if p.x >= p.z {
+                ([1, 0, 0], [1, 0, 1])
+            } else {
+                ([0, 0, 1], [1, 0, 1])
+            }
+        } else if p.y < p.z {
+            ([0, 0, 1], [0, 1, 1])
+        } else if p.x < p.z {
+            ([0, 1, 0], [0, 1, 1])
+        } else {
+            ([0, 1, 0], [1, 1, 0])
+        };
+
+        let mut value = 0.0;
+        let mut gradient_sum = Vec3::zero();
+        for (i, &offset) in [[0, 0, 0], second, third, [1, 1, 1]].iter().enumerate() {
+            let d = p - offset_vector(offset) + Vec3::one() * (i as f32 * G3);
+            // Each corner contributes within a radius which doesn't reach beyond the tetrahedron
+            let falloff = 0.5 - d.dot(d);
+            if falloff > 0.0 {
+                let g = gradient(self.permutation.hash(cell, offset));
+                let gd = g.dot(d);
+                let falloff2 = falloff * falloff;
+                let falloff4 = falloff2 * falloff2;
+                value += falloff4 * gd;
+                gradient_sum = gradient_sum + g * falloff4 - d * (8.0 * falloff2 * falloff * gd);
+            }
+        }
+
+        (value * SIMPLEX_SCALE, gradient_sum * SIMPLEX_SCALE)
+    }
+}
+
+/// Value noise, which smoothly interpolates random values at each integer lattice point.
+///
+/// Values lie within -1 to 1. It is the cheapest of the noises, but its features visibly line up
+/// with the lattice.
+#[derive(Debug, Clone)]
+pub struct Value {
+    permutation: Permutation,
+}
+
+impl Value {
+    /// Create value noise, which is the same for every run with the same seed
+    pub fn new(seed: u32) -> Self {
+        Self {
+            permutation: Permutation::new(seed),
+        }
+    }
+}
+
+impl Noise for Value {
+    fn sample_with_gradient(&self, x: f32, y: f32, z: f32) -> (f32, Vec3) {
+        let (cell, f) = lattice_cell(x, y, z);
+        let mut values = [0.0; 8];
+        for (corner, value) in values.iter_mut().enumerate() {
+            let hash = self.permutation.hash(cell, corner_offset(corner));
+            *value = hash as f32 / 127.5 - 1.0;
+        }
+
+        interpolate(f, &values, &[Vec3::zero(); 8])
+    }
+}
+
+/// Fractional Brownian motion, which sums octaves of a noise at increasing frequencies and
+/// decreasing amplitudes, to add detail at every scale.
+#[derive(Debug, Clone)]
+pub struct Fbm<N> {
+    /// The noise summed at each octave
+    pub noise: N,
+    /// The number of octaves to sum
+    pub octaves: usize,
+    /// The frequency of the first octave
+    pub frequency: f32,
+    /// The factor by which the frequency increases with each octave
+    pub lacunarity: f32,
+    /// The factor by which the amplitude decreases with each octave
+    pub gain: f32,
+}
+
+impl<N: Noise> Fbm<N> {
+    /// Create fractional Brownian motion from the given number of octaves of a noise, each at
+    /// twice the frequency and half the amplitude of the last
+    pub fn new(noise: N, octaves: usize) -> Self {
+        Self {
+            noise,
+            octaves,
+            frequency: 1.0,
+            lacunarity: 2.0,
+            gain: 0.5,
+        }
+    }
+}
+
+impl<N: Noise> Noise for Fbm<N> {
+    fn sample_with_gradient(&self, x: f32, y: f32, z: f32) -> (f32, Vec3) {
+        let mut value = 0.0;
+        let mut gradient = Vec3::zero();
+        let mut frequency = self.frequency;
+        let mut amplitude = 1.0;
+        for octave in 0..self.octaves {
+            let (v, g) = sample_octave(&self.noise, x, y, z, octave, frequency);
+            value += v * amplitude;
+            gradient = gradient + g * (amplitude * frequency);
+            frequency *= self.lacunarity;
+            amplitude *= self.gain;
+        }
+
+        (value, gradient)
+    }
+}
+
+/// F. Kenton Musgrave's ridged multifractal, which folds each octave of a noise into sharp ridges,
+/// and weights each octave by the last, so that detail gathers along the ridges, as in mountain
+/// ranges.
+///
+/// Unlike the other noises, values are never negative, and reach up to around twice `offset`
+/// squared along the highest ridges.
+#[derive(Debug, Clone)]
+pub struct RidgedMultifractal<N> {
+    /// The noise folded into ridges at each octave
+    pub noise: N,
+    /// The number of octaves to sum
+    pub octaves: usize,
+    /// The frequency of the first octave
+    pub frequency: f32,
+    /// The factor by which the frequency increases with each octave
+    pub lacunarity: f32,
+    /// How strongly each octave is weighted by the octave before it
+    pub gain: f32,
+    /// Lifts the ridges, which are where the noise crosses zero
+    pub offset: f32,
+    /// The fractal increment, which decides how quickly the amplitude falls off with each octave
+    pub exponent: f32,
+}
+
+impl<N: Noise> RidgedMultifractal<N> {
+    /// Create a ridged multifractal from the given number of octaves of a noise, with Musgrave's
+    /// suggested parameters
+    pub fn new(noise: N, octaves: usize) -> Self {
+        Self {
+            noise,
+            octaves,
+            frequency: 1.0,
+            lacunarity: 2.0,
+            gain: 2.0,
+            offset: 1.0,
+            exponent: 1.0,
+        }
+    }
+}
+
+impl<N: Noise> Noise for RidgedMultifractal<N> {
+    fn sample_with_gradient(&self, x: f32, y: f32, z: f32) -> (f32, Vec3) {
+        let mut value = 0.0;
+        let mut gradient = Vec3::zero();
+        let mut frequency = self.frequency;
+        let amplitude_factor = self.lacunarity.powf(-self.exponent);
+        let mut amplitude = 1.0;
+        let mut weight = 1.0;
+        let mut weight_gradient = Vec3::zero();
+        for octave in 0..self.octaves {
+            let (v, g) = sample_octave(&self.noise, x, y, z, octave, frequency);
+            let g = g * frequency;
+
+            let ridge = self.offset - v.abs();
+            let ridge_gradient = g * -(v.signum() * 2.0 * ridge);
+            let signal = ridge * ridge * weight;
+            let signal_gradient = ridge_gradient * weight + weight_gradient * (ridge * ridge);
+            value += signal * amplitude;
+            gradient = gradient + signal_gradient * amplitude;
+
+            // Weight the next octave by this one, so that detail gathers along the ridges
+            weight = signal * self.gain;
+            weight_gradient = signal_gradient * self.gain;
+            if weight > 1.0 {
+                weight = 1.0;
+                weight_gradient = Vec3::zero();
+            } else if weight < 0.0 {
+                weight = 0.0;
+                weight_gradient = Vec3::zero();
+            }
+
+            frequency *= self.lacunarity;
+            amplitude *= amplitude_factor;
+        }
+
+        (value, gradient)
+    }
+}
+
+/// Warps a noise by offsetting each sample by a second noise, as per Inigo Quilez's
+/// [Domain Warping](https://iquilezles.org/articles/warp/), which turns regular noise into
+/// swirling, eroded looking shapes.
+#[derive(Debug, Clone)]
+pub struct DomainWarp<S, W> {
+    /// The noise sampled at the displaced positions
+    pub source: S,
+    /// The noise displacing each sample, sampled separately for each axis
+    pub warp: W,
+    /// How far the warp may displace each sample
+    pub strength: f32,
+}
+
+impl<S: Noise, W: Noise> DomainWarp<S, W> {
+    /// Create an adaptor which samples `source` at positions displaced by `strength` times
+    /// `warp`, sampled separately for each axis
+    pub fn new(source: S, warp: W, strength: f32) -> Self {
+        Self {
+            source,
+            warp,
+            strength,
+        }
+    }
+}
+
+impl<S: Noise, W: Noise> Noise for DomainWarp<S, W> {
+    fn sample_with_gradient(&self, x: f32, y: f32, z: f32) -> (f32, Vec3) {
+        let mut q = Vec3::new(x, y, z);
+        let mut warp_gradients = [Vec3::zero(); 3];
+        for axis in 0..3 {
+            let (w, g) = sample_octave(&self.warp, x, y, z, axis + 1, 1.0);
+            q[axis] += w * self.strength;
+            warp_gradients[axis] = g * self.strength;
+        }
+
+        // The chain rule, through the jacobian of the warp
+        let (v, g) = self.source.sample_with_gradient(q.x, q.y, q.z);
+        let gradient =
+            g + warp_gradients[0] * g.x + warp_gradients[1] * g.y + warp_gradients[2] * g.z;
+
+        (v, gradient)
+    }
+}
+
+/// Adapts a noise to a terrain, by treating it as the height of the ground above the xz plane.
+///
+/// The y axis is up, and the ground lies at `y = scale * noise(x, 0, z)`. The distance to the
+/// ground is estimated from the slope of the terrain, which gives a good approximation of a
+/// signed distance field as long as the terrain is reasonably smooth.
+///
+/// Normals are the normal of the ground directly above or below the point, found from the analytic
+/// slope of the terrain without any extra samples of the noise. On the ground this is exactly the
+/// gradient of the estimated distance, which is where surface extraction needs it.
+#[derive(Debug, Clone)]
+pub struct Heightfield<N> {
+    /// The noise giving the height of the terrain, sampled in the xz plane
+    pub noise: N,
+    /// Scales the noise to the height of the terrain
+    pub scale: f32,
+}
+
+impl<N: Noise> Heightfield<N> {
+    /// Create a terrain whose height is `scale` times the given noise
+    pub fn new(noise: N, scale: f32) -> Self {
+        Self { noise, scale }
+    }
+
+    // The height of the terrain above the given point, and its slope
+    fn height(&self, x: f32, z: f32) -> (f32, Vec3) {
+        let (h, g) = self.noise.sample_with_gradient(x, 0.0, z);
+        (h * self.scale, g * self.scale)
+    }
+}
+
+impl<N: Noise> Source for Heightfield<N> {
+    fn sample(&self, x: f32, y: f32, z: f32) -> f32 {
+        let (h, g) = self.height(x, z);
+        (y - h) / (1.0 + g.x * g.x + g.z * g.z).sqrt()
+    }
+}
+
+impl<N: Noise> HermiteSource for Heightfield<N> {
+    fn sample_normal(&self, x: f32, _y: f32, z: f32) -> Vec3 {
+        let (_, g) = self.height(x, z);
+        let s = (1.0 + g.x * g.x + g.z * g.z).sqrt();
+        Vec3::new(-g.x, 1.0, -g.z) * (1.0 / s)
+    }
+}
+
+impl Source for Perlin {
+    fn sample(&self, x: f32, y: f32, z: f32) -> f32 {
+        self.sample_with_gradient(x, y, z).0
+    }
+}
+
+impl HermiteSource for Perlin {
+    fn sample_normal(&self, x: f32, y: f32, z: f32) -> Vec3 {
+        self.sample_with_gradient(x, y, z).1
+    }
+}
+
+impl Source for Simplex {
+    fn sample(&self, x: f32, y: f32, z: f32) -> f32 {
+        self.sample_with_gradient(x, y, z).0
+    }
+}
+
+impl HermiteSource for Simplex {
+    fn sample_normal(&self, x: f32, y: f32, z: f32) -> Vec3 {
+        self.sample_with_gradient(x, y, z).1
+    }
+}
+
+impl Source for Value {
+    fn sample(&self, x: f32, y: f32, z: f32) -> f32 {
+        self.sample_with_gradient(x, y, z).0
+    }
+}
+
+impl HermiteSource for Value {
+    fn sample_normal(&self, x: f32, y: f32, z: f32) -> Vec3 {
+        self.sample_with_gradient(x, y, z).1
+    }
+}
+
+impl<N: Noise> Source for Fbm<N> {
+    fn sample(&self, x: f32, y: f32, z: f32) -> f32 {
+        self.sample_with_gradient(x, y, z).0
+    }
+}
+
+impl<N: Noise> HermiteSource for Fbm<N> {
+    fn sample_normal(&self, x: f32, y: f32, z: f32) -> Vec3 {
+        self.sample_with_gradient(x, y, z).1
+    }
+}
+
+impl<N: Noise> Source for RidgedMultifractal<N> {
+    fn sample(&self, x: f32, y: f32, z: f32) -> f32 {
+        self.sample_with_gradient(x, y, z).0
+    }
+}
+
+impl<N: Noise> HermiteSource for RidgedMultifractal<N> {
+    fn sample_normal(&self, x: f32, y: f32, z: f32) -> Vec3 {
+        self.sample_with_gradient(x, y, z).1
+    }
+}
+
+impl<S: Noise, W: Noise> Source for DomainWarp<S, W> {
+    fn sample(&self, x: f32, y: f32, z: f32) -> f32 {
+        self.sample_with_gradient(x, y, z).0
+    }
+}
+
+impl<S: Noise, W: Noise> HermiteSource for DomainWarp<S, W> {
+    fn sample_normal(&self, x: f32, y: f32, z: f32) -> Vec3 {
+        self.sample_with_gradient(x, y, z).1
+    }
+}
+
+// A shuffled table of the bytes 0 to 255, from which lattice points are hashed
+#[derive(Debug, Clone)]
+struct Permutation([u8; 256]);
+
+impl Permutation {
+    fn new(seed: u32) -> Self {
+        let mut table = [0; 256];
+        for (i, entry) in table.iter_mut().enumerate() {
+            *entry = i as u8;
+        }
+
+        // Shuffle with splitmix64, so that each seed produces the same table on every platform
+        let mut state = u64::from(seed);
+        for i in (1..256).rev() {
+            state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut r = state;
+            r = (r ^ (r >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            r = (r ^ (r >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            r ^= r >> 31;
+            table.swap(i, (r % (i as u64 + 1)) as usize);
+        }
+
+        Permutation(table)
+    }
+
+    fn hash(&self, cell: [i32; 3], offset: [i32; 3]) -> usize {
+        let p = &self.0;
+        let a = i32::from(p[((cell[0] + offset[0]) & 255) as usize]);
+        let b = i32::from(p[((a + cell[1] + offset[1]) & 255) as usize]);
+        p[((b + cell[2] + offset[2]) & 255) as usize] as usize
+    }
+}
+
+fn gradient(hash: usize) -> Vec3 {
+    let g = GRADIENTS[hash & 15];
+    Vec3::new(g[0], g[1], g[2])
+}
+
+// The integer lattice cell containing a point, and the position of the point within it
+fn lattice_cell(x: f32, y: f32, z: f32) -> ([i32; 3], Vec3) {
+    let cell = Vec3::new(x.floor(), y.floor(), z.floor());
+    (
+        [cell.x as i32, cell.y as i32, cell.z as i32],
+        Vec3::new(x, y, z) - cell,
+    )
+}
+
+fn corner_offset(corner: usize) -> [i32; 3] {
+    [
+        (corner & 1) as i32,
+        ((corner >> 1) & 1) as i32,
+        ((corner >> 2) & 1) as i32,
+    ]
+}
+
+fn offset_vector(offset: [i32; 3]) -> Vec3 {
+    Vec3::new(offset[0] as f32, offset[1] as f32, offset[2] as f32)
+}
+
+// Sample one octave of a fractal, shifted so that it doesn't line up with the other octaves
+fn sample_octave<N: Noise>(
+    noise: &N,
+    x: f32,
+    y: f32,
+    z: f32,
+    octave: usize,
+    frequency: f32,
+) -> (f32, Vec3) {
+    let shift = Vec3::new(OCTAVE_SHIFT[0], OCTAVE_SHIFT[1], OCTAVE_SHIFT[2]) * octave as f32;
+    let p = Vec3::new(x, y, z) * frequency + shift;
+    noise.sample_with_gradient(p.x, p.y, p.z)
+}
+
+// Ken Perlin's quintic fade curve, and its derivative
+fn fade(t: f32) -> (f32, f32) {
+    (
+        t * t * t * (t * (t * 6.0 - 15.0) + 10.0),
+        30.0 * t * t * (t * (t - 2.0) + 1.0),
+    )
+}
+
+// Blend the values at the 8 corners of a lattice cell, along with the gradients of those values,
+// into the value and gradient at the position `f` within the cell
+fn interpolate(f: Vec3, values: &[f32; 8], gradients: &[Vec3; 8]) -> (f32, Vec3) {
+    let (ux, dx) = fade(f.x);
+    let (uy, dy) = fade(f.y);
+    let (uz, dz) = fade(f.z);
+
+    let mut value = 0.0;
+    let mut gradient = Vec3::zero();
+    for corner in 0..8 {
+        let (wx, dwx) = if corner & 1 == 0 {
+            (1.0 - ux, -dx)
+        } else {
+            (ux, dx)
+        };
+        let (wy, dwy) = if corner & 2 == 0 {
+            (1.0 - uy, -dy)
+        } else {
+            (uy, dy)
+        };
+        let (wz, dwz) = if corner & 4 == 0 {
+            (1.0 - uz, -dz)
+        } else {
+            (uz, dz)
+        };
+
+        let weight = wx * wy * wz;
+        let weight_gradient = Vec3::new(dwx * wy * wz, wx * dwy * wz, wx * wy * dwz);
+        value += weight * values[corner];
+        gradient = gradient + gradients[corner] * weight + weight_gradient * values[corner];
+    }
+
+    (value, gradient)
+}
@@ -14,28 +14,52 @@
 
 use source::{HermiteSource, Source};
 use marching_cubes_tables::CORNERS;
+use math::Vec3;
+use region::Region;
+use sink::MeshSink;
 
 /// Extracts point clouds from distance fields.
 pub struct PointCloud {
-    size: usize,
+    region: Region,
+    iso_level: f32,
     layers: [Vec<f32>; 2],
 }
 
 impl PointCloud {
     /// Create a new PointCloud with the given chunk size.
     ///
-    /// For a given `size`, this will evaluate chunks of `size^3` voxels.
+    /// For a given `size`, this will evaluate chunks of `size^3` voxels, spanning the unit cube.
     pub fn new(size: usize) -> Self {
+        PointCloud::with_region(Region::unit(size))
+    }
+
+    /// Create a new PointCloud which samples the given [`Region`](../region/struct.Region.html).
+    pub fn with_region(region: Region) -> Self {
+        let layer_size = region.samples[0] * region.samples[1];
         PointCloud {
-            size,
-            layers: [vec![0f32; size * size], vec![0f32; size * size]],
+            region,
+            iso_level: 0.0,
+            layers: [vec![0f32; layer_size], vec![0f32; layer_size]],
         }
     }
 
+    /// Set the iso-level at which to extract the surface.
+    ///
+    /// Defaults to zero, which is the surface of a signed distance field. Samples at or below the
+    /// iso-level are considered to be inside the surface.
+    pub fn set_iso_level(&mut self, iso_level: f32) {
+        self.iso_level = iso_level;
+    }
+
+    /// The iso-level at which the surface is extracted
+    pub fn iso_level(&self) -> f32 {
+        self.iso_level
+    }
+
     /// Extracts a point cloud from the given [`Source`](../source/trait.Source.html).
     ///
-    /// The Source will be sampled in the range (0,0,0) to (1,1,1), with the number of steps
-    /// determined by the size provided to the constructor.
+    /// The Source will be sampled across the region provided to the constructor, which defaults
+    /// to the range (0,0,0) to (1,1,1), and extracted points will lie within that region.
     ///
     /// The midpoints of extracted voxels will be appended to `vertices` as triples of (x, y, z)
     /// coordinates.
@@ -52,8 +76,8 @@ impl PointCloud {
 
     /// Extracts a point cloud with normal data from the given [`HermiteSource`](../source/trait.HermiteSource.html).
     ///
-    /// The Source will be sampled in the range (0,0,0) to (1,1,1), with the number of steps
-    /// determined by the size provided to the constructor.
+    /// The Source will be sampled across the region provided to the constructor, which defaults
+    /// to the range (0,0,0) to (1,1,1), and extracted points will lie within that region.
     ///
     /// The midpoints of extracted voxels will be appended to `vertices` as triples of (x, y, z)
     /// coordinates, followed by the surface normals as triples of (x, y, z) dimensions.
@@ -72,47 +96,63 @@ impl PointCloud {
         });
     }
 
+    /// Extracts a point cloud from the given [`Source`](../source/trait.Source.html) into the
+    /// given [`MeshSink`](../sink/trait.MeshSink.html).
+    ///
+    /// Only vertices are added to the sink, one for the midpoint of each extracted voxel.
+    pub fn extract_midpoints_to<S, M>(&mut self, source: &S, sink: &mut M)
+    where
+        S: Source,
+        M: MeshSink,
+    {
+        self.extract_impl(source, |x: f32, y: f32, z: f32| {
+            sink.add_vertex(Vec3::new(x, y, z), None);
+        });
+    }
+
+    /// Extracts a point cloud with normal data from the given [`HermiteSource`](../source/trait.HermiteSource.html)
+    /// into the given [`MeshSink`](../sink/trait.MeshSink.html).
+    ///
+    /// Only vertices are added to the sink, one for the midpoint of each extracted voxel.
+    pub fn extract_midpoints_with_normals_to<S, M>(&mut self, source: &S, sink: &mut M)
+    where
+        S: HermiteSource,
+        M: MeshSink,
+    {
+        self.extract_impl(source, |x: f32, y: f32, z: f32| {
+            sink.add_vertex(Vec3::new(x, y, z), Some(source.sample_normal(x, y, z)));
+        });
+    }
+
     fn extract_impl<S, E>(&mut self, source: &S, mut extract: E)
     where
         S: Source,
         E: FnMut(f32, f32, f32) -> (),
     {
-        let size_minus_one = self.size - 1;
-        let one_over_size = 1.0 / (size_minus_one as f32);
+        let [size_x, size_y, size_z] = self.region.samples;
+        let origin = self.region.origin;
+        let step = self.region.step();
 
         // Cache layer zero of distance field values
-        for y in 0usize..self.size {
-            for x in 0..self.size {
-                self.layers[0][y * self.size + x] =
-                    source.sample(x as f32 * one_over_size, y as f32 * one_over_size, 0.0);
-            }
-        }
+        source.sample_layer(&self.region, 0, &mut self.layers[0]);
 
         let mut values = [0f32; 8];
 
-        for z in 0..self.size {
+        for z in 0..size_z - 1 {
             // Cache layer N+1 of isosurface values
-            for y in 0..self.size {
-                for x in 0..self.size {
-                    self.layers[1][y * self.size + x] = source.sample(
-                        x as f32 * one_over_size,
-                        y as f32 * one_over_size,
-                        (z + 1) as f32 * one_over_size,
-                    );
-                }
-            }
+            source.sample_layer(&self.region, z + 1, &mut self.layers[1]);
 
             // Extract the cells in the current layer
-            for y in 0..size_minus_one {
-                for x in 0..size_minus_one {
+            for y in 0..size_y - 1 {
+                for x in 0..size_x - 1 {
                     for i in 0..8 {
                         values[i] = self.layers[CORNERS[i][2]]
-                            [(y + CORNERS[i][1]) * self.size + x + CORNERS[i][0]];
+                            [(y + CORNERS[i][1]) * size_x + x + CORNERS[i][0]];
                     }
 
                     let mut cube_index = 0;
                     for i in 0usize..8 {
-                        if values[i] <= 0.0 {
+                        if values[i] <= self.iso_level {
                             cube_index |= 1 << i;
                         }
                     }
@@ -121,9 +161,9 @@ impl PointCloud {
                         continue;
                     }
 
-                    let px = (x as f32 + 0.5) * one_over_size;
-                    let py = (y as f32 + 0.5) * one_over_size;
-                    let pz = (z as f32 + 0.5) * one_over_size;
+                    let px = origin.x + (x as f32 + 0.5) * step.x;
+                    let py = origin.y + (y as f32 + 0.5) * step.y;
+                    let pz = origin.z + (z as f32 + 0.5) * step.z;
 
                     extract(px, py, pz);
                 }
@@ -0,0 +1,166 @@
+// Copyright 2018 Tristam MacDonald
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use math::Vec3;
+
+// Eigenvalues smaller than this are treated as zero when inverting the QEF matrix. This is what
+// keeps vertices on flat or cylindrical patches from shooting off along the unconstrained axes.
+const EIGENVALUE_TOLERANCE: f32 = 0.1;
+
+// Number of Jacobi sweeps. A 3x3 matrix converges well within this many.
+const JACOBI_SWEEPS: usize = 6;
+
+/// Accumulates the quadratic error function of a set of tangent planes, and finds the point
+/// that best fits them.
+///
+/// Each plane is given by a point on the surface and the surface normal at that point. The
+/// minimiser is computed relative to the mass point (the average of all the plane points) using a
+/// truncated pseudo-inverse, which gives the solution nearest the mass point when the planes do
+/// not fully constrain it.
+pub struct Qef {
+    // Upper triangle of the symmetric matrix A^T A, as [xx, xy, xz, yy, yz, zz]
+    ata: [f32; 6],
+    atb: Vec3,
+    point_sum: Vec3,
+    count: usize,
+}
+
+impl Qef {
+    /// Create an empty QEF
+    pub fn new() -> Self {
+        Self {
+            ata: [0.0; 6],
+            atb: Vec3::zero(),
+            point_sum: Vec3::zero(),
+            count: 0,
+        }
+    }
+
+    /// Add the tangent plane through `point` with the given `normal`
+    pub fn add(&mut self, point: Vec3, normal: Vec3) {
+        let n = normal.normalize();
+        let d = n.dot(point);
+
+        self.ata[0] += n.x * n.x;
+        self.ata[1] += n.x * n.y;
+        self.ata[2] += n.x * n.z;
+        self.ata[3] += n.y * n.y;
+        self.ata[4] += n.y * n.z;
+        self.ata[5] += n.z * n.z;
+
+        self.atb = self.atb + n * d;
+        self.point_sum = self.point_sum + point;
+        self.count += 1;
+    }
+
+    /// The average of all the points added to the QEF
+    pub fn mass_point(&self) -> Vec3 {
+        if self.count > 0 {
+            self.point_sum * (1.0 / self.count as f32)
+        } else {
+            Vec3::zero()
+        }
+    }
+
+    /// Find the point minimising the squared distance to all the tangent planes
+    pub fn solve(&self) -> Vec3 {
+        let mass_point = self.mass_point();
+
+        // Shift the problem so that we solve for an offset from the mass point
+        let a = &self.ata;
+        let residual = self.atb - Vec3::new(
+            a[0] * mass_point.x + a[1] * mass_point.y + a[2] * mass_point.z,
+            a[1] * mass_point.x + a[3] * mass_point.y + a[4] * mass_point.z,
+            a[2] * mass_point.x + a[4] * mass_point.y + a[5] * mass_point.z,
+        );
+
+        let (eigenvalues, eigenvectors) = symmetric_eigen(self.ata);
+
+        let mut offset = Vec3::zero();
+        for i in 0..3 {
+            if eigenvalues[i].abs() < EIGENVALUE_TOLERANCE {
+                continue;
+            }
+            let v = Vec3::new(
+                eigenvectors[0][i],
+                eigenvectors[1][i],
+                eigenvectors[2][i],
+            );
+            offset = offset + v * (v.dot(residual) / eigenvalues[i]);
+        }
+
+        mass_point + offset
+    }
+}
+
+/// Keep a QEF solution inside the cell that generated it.
+///
+/// Minimisers can land well outside the cell when the tangent planes are nearly parallel, which
+/// folds the resulting mesh over itself.
+pub fn clamp_to_cell(vertex: Vec3, min: Vec3, max: Vec3) -> Vec3 {
+    Vec3::new(
+        vertex.x.max(min.x).min(max.x),
+        vertex.y.max(min.y).min(max.y),
+        vertex.z.max(min.z).min(max.z),
+    )
+}
+
+/// Diagonalise a symmetric 3x3 matrix (given as its upper triangle) using Jacobi rotations.
+///
+/// Returns the eigenvalues, and a matrix whose columns are the corresponding eigenvectors.
+fn symmetric_eigen(upper: [f32; 6]) -> ([f32; 3], [[f32; 3]; 3]) {
+    let mut a = [
+        [upper[0], upper[1], upper[2]],
+        [upper[1], upper[3], upper[4]],
+        [upper[2], upper[4], upper[5]],
+    ];
+    let mut v = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+
+    for _ in 0..JACOBI_SWEEPS {
+        for &(p, q) in &[(0, 1), (0, 2), (1, 2)] {
+            if a[p][q].abs() < 1.0e-12 {
+                continue;
+            }
+
+            let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+            let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+            let c = 1.0 / (t * t + 1.0).sqrt();
+            let s = t * c;
+
+            for row in a.iter_mut() {
+                let akp = row[p];
+                let akq = row[q];
+                row[p] = c * akp - s * akq;
+                row[q] = s * akp + c * akq;
+            }
+            {
+                // p is always less than q, so the rows can be borrowed separately
+                let (upper, lower) = a.split_at_mut(q);
+                for (apk, aqk) in upper[p].iter_mut().zip(lower[0].iter_mut()) {
+                    let (x, y) = (*apk, *aqk);
+                    *apk = c * x - s * y;
+                    *aqk = s * x + c * y;
+                }
+            }
+            for row in v.iter_mut() {
+                let vkp = row[p];
+                let vkq = row[q];
+                row[p] = c * vkp - s * vkq;
+                row[q] = s * vkp + c * vkq;
+            }
+        }
+    }
+
+    ([a[0][0], a[1][1], a[2][2]], v)
+}
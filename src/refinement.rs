@@ -0,0 +1,188 @@
+// Copyright 2018 Tristam MacDonald
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use math::Vec3;
+use source::{BoundedSource, HermiteSource, Source};
+
+/// An octree node being considered for refinement
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct OctreeNode {
+    /// The depth of the node, where the root of the octree is at level 0
+    pub level: usize,
+    /// The centre of the node
+    pub centre: Vec3,
+    /// Half the size of the node along each axis
+    pub half_size: Vec3,
+    /// The value of the source at the centre of the node, relative to the iso-level
+    pub value: f32,
+    /// The iso-level at which the surface is being extracted
+    pub iso_level: f32,
+}
+
+impl OctreeNode {
+    /// Whether the surface may pass through this node, assuming the source is a signed distance
+    /// field
+    pub fn may_contain_surface(&self) -> bool {
+        self.value.abs() <= self.half_size.length()
+    }
+}
+
+/// Decides which nodes of the octree to split while building it.
+///
+/// The octree is always refined to at least level 2, and never beyond its maximum depth, whatever
+/// the policy decides.
+pub trait RefinementPolicy<S: ?Sized> {
+    /// Whether to split the given node into 8 children, given the source being extracted
+    fn should_refine(&self, source: &S, node: &OctreeNode) -> bool;
+}
+
+/// Refines every node the surface may pass through, which spends resolution uniformly along the
+/// whole surface. This is the default.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Distance;
+
+impl<S: Source + ?Sized> RefinementPolicy<S> for Distance {
+    fn should_refine(&self, _source: &S, node: &OctreeNode) -> bool {
+        node.may_contain_surface()
+    }
+}
+
+/// Refines nodes the surface passes through only while the surface normal varies across them by
+/// more than a given angle, which spends resolution on curved and creased parts of the surface,
+/// and leaves flat parts coarse.
+#[derive(Debug, Copy, Clone)]
+pub struct NormalDeviation {
+    min_cos_angle: f32,
+}
+
+impl NormalDeviation {
+    /// Create a policy which refines nodes while the normals at their corners deviate from the
+    /// normal at their centre by more than `max_angle` radians
+    pub fn new(max_angle: f32) -> Self {
+        Self {
+            min_cos_angle: max_angle.cos(),
+        }
+    }
+}
+
+impl<S: HermiteSource + ?Sized> RefinementPolicy<S> for NormalDeviation {
+    fn should_refine(&self, source: &S, node: &OctreeNode) -> bool {
+        if !node.may_contain_surface() {
+            return false;
+        }
+
+        let c = node.centre;
+        let normal = source.sample_normal(c.x, c.y, c.z).normalize();
+        (0..8).any(|i| {
+            let corner = Vec3::new(
+                if i & 1 == 0 { -1.0 } else { 1.0 },
+                if i & 2 == 0 { -1.0 } else { 1.0 },
+                if i & 4 == 0 { -1.0 } else { 1.0 },
+            );
+            let p = c + corner * node.half_size;
+            let n = source.sample_normal(p.x, p.y, p.z).normalize();
+            n.dot(normal) < self.min_cos_angle
+        })
+    }
+}
+
+/// Refines nodes the surface may pass through until they appear no larger than a given angle from
+/// the camera, so that the surface is finest closest to the viewer.
+#[derive(Debug, Copy, Clone)]
+pub struct ScreenSpaceError {
+    camera: Vec3,
+    max_error: f32,
+}
+
+impl ScreenSpaceError {
+    /// Create a policy for a camera at the given position, which refines nodes until their
+    /// diagonal subtends no more than `max_error` radians (approximately)
+    pub fn new(camera: Vec3, max_error: f32) -> Self {
+        Self { camera, max_error }
+    }
+
+    /// Move the camera
+    pub fn set_camera(&mut self, camera: Vec3) {
+        self.camera = camera;
+    }
+
+    /// The position of the camera
+    pub fn camera(&self) -> Vec3 {
+        self.camera
+    }
+}
+
+impl<S: Source + ?Sized> RefinementPolicy<S> for ScreenSpaceError {
+    fn should_refine(&self, _source: &S, node: &OctreeNode) -> bool {
+        let diagonal = node.half_size.length() * 2.0;
+        let distance = (node.centre - self.camera).length();
+        node.may_contain_surface() && diagonal > self.max_error * distance
+    }
+}
+
+/// Refines nodes the surface may pass through to the maximum depth inside a box, and only to a
+/// coarser depth outside it.
+#[derive(Debug, Copy, Clone)]
+pub struct RegionOfInterest {
+    min: Vec3,
+    max: Vec3,
+    outside_depth: usize,
+}
+
+impl RegionOfInterest {
+    /// Create a policy which refines the surface fully within the box from `min` to `max`, and
+    /// only down to `outside_depth` elsewhere
+    pub fn new(min: Vec3, max: Vec3, outside_depth: usize) -> Self {
+        Self {
+            min,
+            max,
+            outside_depth,
+        }
+    }
+}
+
+impl<S: Source + ?Sized> RefinementPolicy<S> for RegionOfInterest {
+    fn should_refine(&self, _source: &S, node: &OctreeNode) -> bool {
+        let lower = node.centre - node.half_size;
+        let upper = node.centre + node.half_size;
+        let overlaps =
+            (0..3).all(|axis| lower[axis] <= self.max[axis] && upper[axis] >= self.min[axis]);
+        node.may_contain_surface() && (overlaps || node.level < self.outside_depth)
+    }
+}
+
+/// Wraps another policy, and never refines nodes which a
+/// [BoundedSource](../source/trait.BoundedSource.html) proves the surface can't pass through.
+///
+/// This prunes whole subtrees of empty space, even for sources which aren't distance fields, or
+/// for policies which would otherwise refine them.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Pruned<R> {
+    pub policy: R,
+}
+
+impl<R> Pruned<R> {
+    /// Create a policy which prunes the nodes that the given policy would refine
+    pub fn new(policy: R) -> Self {
+        Self { policy }
+    }
+}
+
+impl<S: BoundedSource + ?Sized, R: RefinementPolicy<S>> RefinementPolicy<S> for Pruned<R> {
+    fn should_refine(&self, source: &S, node: &OctreeNode) -> bool {
+        let interval =
+            source.sample_interval(node.centre - node.half_size, node.centre + node.half_size);
+        interval.contains(node.iso_level) && self.policy.should_refine(source, node)
+    }
+}
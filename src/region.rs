@@ -0,0 +1,67 @@
+// Copyright 2018 Tristam MacDonald
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use math::Vec3;
+
+/// An axis-aligned box of space, and the grid of points at which to sample it.
+///
+/// Samples are spaced evenly along each axis, with the first sample at `origin` and the last at
+/// `origin + extent`. The spacing may differ between axes, which allows for non-cubic and
+/// anisotropic grids.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Region {
+    /// The minimum corner of the box
+    pub origin: Vec3,
+    /// The size of the box along each axis
+    pub extent: Vec3,
+    /// The number of samples along each axis, including both ends. Must be at least 2.
+    pub samples: [usize; 3],
+}
+
+impl Region {
+    /// Create a region covering the box from `origin` to `origin + extent`, sampled at the given
+    /// number of points along each axis.
+    pub fn new(origin: Vec3, extent: Vec3, samples: [usize; 3]) -> Region {
+        Region {
+            origin,
+            extent,
+            samples,
+        }
+    }
+
+    /// Create a region covering the unit cube (0,0,0) to (1,1,1), sampled at `size` points along
+    /// each axis.
+    pub fn unit(size: usize) -> Region {
+        Region::new(Vec3::zero(), Vec3::one(), [size, size, size])
+    }
+
+    /// The distance between adjacent samples along each axis
+    pub fn step(&self) -> Vec3 {
+        Vec3::new(
+            self.extent.x / (self.samples[0] - 1) as f32,
+            self.extent.y / (self.samples[1] - 1) as f32,
+            self.extent.z / (self.samples[2] - 1) as f32,
+        )
+    }
+
+    /// The position of the sample with the given integer coordinates
+    pub fn position(&self, x: usize, y: usize, z: usize) -> Vec3 {
+        let step = self.step();
+        Vec3::new(
+            self.origin.x + x as f32 * step.x,
+            self.origin.y + y as f32 * step.y,
+            self.origin.z + z as f32 * step.z,
+        )
+    }
+}
@@ -0,0 +1,854 @@
+// Copyright 2018 Tristam MacDonald
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use math::{Dual, Interval, Scalar, Vec3};
+use source::{BoundedSource, Field, HermiteSource, Source};
+use std;
+
+/// A sphere, centred on the origin
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Sphere {
+    /// Distance from the centre to the surface
+    pub radius: f32,
+}
+
+impl Sphere {
+    /// Create a sphere with the given radius
+    pub fn new(radius: f32) -> Self {
+        Self { radius }
+    }
+}
+
+impl Field for Sphere {
+    fn evaluate<T: Scalar>(&self, x: T, y: T, z: T) -> T {
+        (x * x + y * y + z * z).sqrt() - self.radius
+    }
+}
+
+impl Source for Sphere {
+    fn sample(&self, x: f32, y: f32, z: f32) -> f32 {
+        self.evaluate(x, y, z)
+    }
+}
+
+impl HermiteSource for Sphere {
+    fn sample_normal(&self, x: f32, y: f32, z: f32) -> Vec3 {
+        gradient(self, x, y, z)
+    }
+}
+
+impl BoundedSource for Sphere {
+    fn sample_interval(&self, min: Vec3, max: Vec3) -> Interval {
+        distance_interval(min, max) - self.radius
+    }
+}
+
+/// An axis-aligned box, centred on the origin
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Cuboid {
+    /// Half the size of the box along each axis
+    pub half_extents: Vec3,
+}
+
+impl Cuboid {
+    /// Create a box extending `half_extents` from the origin along each axis
+    pub fn new(half_extents: Vec3) -> Self {
+        Self { half_extents }
+    }
+}
+
+impl Field for Cuboid {
+    fn evaluate<T: Scalar>(&self, x: T, y: T, z: T) -> T {
+        cuboid(x, y, z, self.half_extents)
+    }
+}
+
+impl Source for Cuboid {
+    fn sample(&self, x: f32, y: f32, z: f32) -> f32 {
+        self.evaluate(x, y, z)
+    }
+}
+
+impl HermiteSource for Cuboid {
+    fn sample_normal(&self, x: f32, y: f32, z: f32) -> Vec3 {
+        gradient(self, x, y, z)
+    }
+}
+
+impl BoundedSource for Cuboid {
+    fn sample_interval(&self, min: Vec3, max: Vec3) -> Interval {
+        lipschitz_interval(self, min, max)
+    }
+}
+
+/// An axis-aligned box with rounded edges and corners, centred on the origin
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct RoundedCuboid {
+    /// Half the size of the box along each axis, including the rounding
+    pub half_extents: Vec3,
+    /// The radius of the rounded edges
+    pub radius: f32,
+}
+
+impl RoundedCuboid {
+    /// Create a box extending `half_extents` from the origin along each axis, with its edges
+    /// rounded off to the given radius
+    pub fn new(half_extents: Vec3, radius: f32) -> Self {
+        Self {
+            half_extents,
+            radius,
+        }
+    }
+}
+
+impl Field for RoundedCuboid {
+    fn evaluate<T: Scalar>(&self, x: T, y: T, z: T) -> T {
+        let inner = self.half_extents - Vec3::one() * self.radius;
+        cuboid(x, y, z, inner) - self.radius
+    }
+}
+
+impl Source for RoundedCuboid {
+    fn sample(&self, x: f32, y: f32, z: f32) -> f32 {
+        self.evaluate(x, y, z)
+    }
+}
+
+impl HermiteSource for RoundedCuboid {
+    fn sample_normal(&self, x: f32, y: f32, z: f32) -> Vec3 {
+        gradient(self, x, y, z)
+    }
+}
+
+impl BoundedSource for RoundedCuboid {
+    fn sample_interval(&self, min: Vec3, max: Vec3) -> Interval {
+        lipschitz_interval(self, min, max)
+    }
+}
+
+/// A torus, centred on the origin, and lying in the xy plane
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Torus {
+    /// The distance from the origin to the centre of the tube
+    pub major_radius: f32,
+    /// The radius of the tube
+    pub minor_radius: f32,
+}
+
+impl Torus {
+    /// Create a torus with the given major and minor radii
+    pub fn new(major_radius: f32, minor_radius: f32) -> Self {
+        Self {
+            major_radius,
+            minor_radius,
+        }
+    }
+}
+
+impl Field for Torus {
+    fn evaluate<T: Scalar>(&self, x: T, y: T, z: T) -> T {
+        let q = (x * x + y * y).sqrt() - self.major_radius;
+        (q * q + z * z).sqrt() - self.minor_radius
+    }
+}
+
+impl Source for Torus {
+    fn sample(&self, x: f32, y: f32, z: f32) -> f32 {
+        self.evaluate(x, y, z)
+    }
+}
+
+impl HermiteSource for Torus {
+    fn sample_normal(&self, x: f32, y: f32, z: f32) -> Vec3 {
+        gradient(self, x, y, z)
+    }
+}
+
+impl BoundedSource for Torus {
+    fn sample_interval(&self, min: Vec3, max: Vec3) -> Interval {
+        lipschitz_interval(self, min, max)
+    }
+}
+
+/// A line segment swept by a sphere
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Capsule {
+    /// Centre of one end
+    pub a: Vec3,
+    /// Centre of the other end
+    pub b: Vec3,
+    /// Distance from the segment `a`-`b` to the surface
+    pub radius: f32,
+}
+
+impl Capsule {
+    /// Create a capsule with hemispherical ends centred on `a` and `b`
+    pub fn new(a: Vec3, b: Vec3, radius: f32) -> Self {
+        Self { a, b, radius }
+    }
+}
+
+impl Field for Capsule {
+    fn evaluate<T: Scalar>(&self, x: T, y: T, z: T) -> T {
+        let (pa_x, pa_y, pa_z) = (x - self.a.x, y - self.a.y, z - self.a.z);
+        let ba = self.b - self.a;
+        let length_squared = ba.dot(ba);
+        let h = if length_squared > 0.0 {
+            clamp(
+                (pa_x * ba.x + pa_y * ba.y + pa_z * ba.z) / length_squared,
+                0.0,
+                1.0,
+            )
+        } else {
+            T::constant(0.0)
+        };
+        let (dx, dy, dz) = (pa_x - h * ba.x, pa_y - h * ba.y, pa_z - h * ba.z);
+        (dx * dx + dy * dy + dz * dz).sqrt() - self.radius
+    }
+}
+
+impl Source for Capsule {
+    fn sample(&self, x: f32, y: f32, z: f32) -> f32 {
+        self.evaluate(x, y, z)
+    }
+}
+
+impl HermiteSource for Capsule {
+    fn sample_normal(&self, x: f32, y: f32, z: f32) -> Vec3 {
+        gradient(self, x, y, z)
+    }
+}
+
+impl BoundedSource for Capsule {
+    fn sample_interval(&self, min: Vec3, max: Vec3) -> Interval {
+        lipschitz_interval(self, min, max)
+    }
+}
+
+/// A capped cylinder, centred on the origin, with its axis along z
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Cylinder {
+    /// Distance from the axis to the curved surface
+    pub radius: f32,
+    /// Half the length of the cylinder along its axis
+    pub half_height: f32,
+}
+
+impl Cylinder {
+    /// Create a cylinder with the given radius, extending `half_height` either side of the origin
+    pub fn new(radius: f32, half_height: f32) -> Self {
+        Self {
+            radius,
+            half_height,
+        }
+    }
+}
+
+impl Field for Cylinder {
+    fn evaluate<T: Scalar>(&self, x: T, y: T, z: T) -> T {
+        let zero = T::constant(0.0);
+        let dx = (x * x + y * y).sqrt() - self.radius;
+        let dy = z.abs() - self.half_height;
+        let (ox, oy) = (dx.max(zero), dy.max(zero));
+        dx.max(dy).min(zero) + (ox * ox + oy * oy).sqrt()
+    }
+}
+
+impl Source for Cylinder {
+    fn sample(&self, x: f32, y: f32, z: f32) -> f32 {
+        self.evaluate(x, y, z)
+    }
+}
+
+impl HermiteSource for Cylinder {
+    fn sample_normal(&self, x: f32, y: f32, z: f32) -> Vec3 {
+        gradient(self, x, y, z)
+    }
+}
+
+impl BoundedSource for Cylinder {
+    fn sample_interval(&self, min: Vec3, max: Vec3) -> Interval {
+        lipschitz_interval(self, min, max)
+    }
+}
+
+/// A capped cone, centred on the origin, with its axis along z
+///
+/// The base lies at `-half_height` along z, and the apex at `+half_height`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Cone {
+    /// The radius of the base of the cone
+    pub radius: f32,
+    /// Half the distance from the base to the apex
+    pub half_height: f32,
+}
+
+impl Cone {
+    /// Create a cone with the given base radius, extending `half_height` either side of the origin
+    pub fn new(radius: f32, half_height: f32) -> Self {
+        Self {
+            radius,
+            half_height,
+        }
+    }
+}
+
+impl Field for Cone {
+    fn evaluate<T: Scalar>(&self, x: T, y: T, z: T) -> T {
+        let h = self.half_height;
+        let r = self.radius;
+        let qx = (x * x + y * y).sqrt();
+        let qy = z;
+
+        // Distance to the base, and to the slanted side
+        let ca_x = qx - qx.min(T::constant(if qy.value() < 0.0 { r } else { 0.0 }));
+        let ca_y = qy.abs() - h;
+        let (k2_x, k2_y) = (-r, 2.0 * h);
+        let t = clamp(
+            (-qx * k2_x + (-qy + h) * k2_y) / (k2_x * k2_x + k2_y * k2_y),
+            0.0,
+            1.0,
+        );
+        let cb_x = qx + t * k2_x;
+        let cb_y = qy - h + t * k2_y;
+
+        let sign = if cb_x.value() < 0.0 && ca_y.value() < 0.0 {
+            -1.0
+        } else {
+            1.0
+        };
+        (ca_x * ca_x + ca_y * ca_y)
+            .min(cb_x * cb_x + cb_y * cb_y)
+            .sqrt()
+            * sign
+    }
+}
+
+impl Source for Cone {
+    fn sample(&self, x: f32, y: f32, z: f32) -> f32 {
+        self.evaluate(x, y, z)
+    }
+}
+
+impl HermiteSource for Cone {
+    fn sample_normal(&self, x: f32, y: f32, z: f32) -> Vec3 {
+        gradient(self, x, y, z)
+    }
+}
+
+impl BoundedSource for Cone {
+    fn sample_interval(&self, min: Vec3, max: Vec3) -> Interval {
+        lipschitz_interval(self, min, max)
+    }
+}
+
+/// An infinite plane
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Plane {
+    /// The unit normal of the plane, pointing away from the solid side
+    pub normal: Vec3,
+    /// The signed distance of the plane from the origin, along the normal
+    pub offset: f32,
+}
+
+impl Plane {
+    /// Create a plane with the given normal, at the given distance from the origin.
+    ///
+    /// The normal is normalised, and everything behind the plane is considered solid.
+    pub fn new(normal: Vec3, offset: f32) -> Self {
+        Self {
+            normal: normal.normalize(),
+            offset,
+        }
+    }
+}
+
+impl Field for Plane {
+    fn evaluate<T: Scalar>(&self, x: T, y: T, z: T) -> T {
+        x * self.normal.x + y * self.normal.y + z * self.normal.z - self.offset
+    }
+}
+
+impl Source for Plane {
+    fn sample(&self, x: f32, y: f32, z: f32) -> f32 {
+        self.evaluate(x, y, z)
+    }
+}
+
+impl HermiteSource for Plane {
+    fn sample_normal(&self, x: f32, y: f32, z: f32) -> Vec3 {
+        gradient(self, x, y, z)
+    }
+}
+
+impl BoundedSource for Plane {
+    fn sample_interval(&self, min: Vec3, max: Vec3) -> Interval {
+        let mut interval = Interval::new(-self.offset, -self.offset);
+        for axis in 0..3 {
+            let a = self.normal[axis] * min[axis];
+            let b = self.normal[axis] * max[axis];
+            interval.min += a.min(b);
+            interval.max += a.max(b);
+        }
+        interval
+    }
+}
+
+/// An axis-aligned ellipsoid, centred on the origin
+///
+/// There is no closed form for the distance to an ellipsoid, so this is an approximation, which is
+/// exact on the surface but grows less accurate away from it, particularly near the centre.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Ellipsoid {
+    /// The radius along each axis
+    pub radii: Vec3,
+}
+
+impl Ellipsoid {
+    /// Create an ellipsoid with the given radius along each axis
+    pub fn new(radii: Vec3) -> Self {
+        Self { radii }
+    }
+}
+
+impl Field for Ellipsoid {
+    fn evaluate<T: Scalar>(&self, x: T, y: T, z: T) -> T {
+        let r = self.radii;
+        let (px, py, pz) = (x / r.x, y / r.y, z / r.z);
+        let k0 = (px * px + py * py + pz * pz).sqrt();
+        let (qx, qy, qz) = (x / (r.x * r.x), y / (r.y * r.y), z / (r.z * r.z));
+        let k1 = (qx * qx + qy * qy + qz * qz).sqrt();
+        if k1.value() > 0.0 {
+            k0 * (k0 - 1.0) / k1
+        } else {
+            T::constant(-r.x.min(r.y).min(r.z))
+        }
+    }
+}
+
+impl Source for Ellipsoid {
+    fn sample(&self, x: f32, y: f32, z: f32) -> f32 {
+        self.evaluate(x, y, z)
+    }
+}
+
+impl HermiteSource for Ellipsoid {
+    fn sample_normal(&self, x: f32, y: f32, z: f32) -> Vec3 {
+        gradient(self, x, y, z)
+    }
+}
+
+impl BoundedSource for Ellipsoid {
+    fn sample_interval(&self, min: Vec3, max: Vec3) -> Interval {
+        // The approximation is (k0 - 1) scaled by k0 / k1, which always lies between the smallest
+        // and largest radii
+        let k0 = distance_interval(min / self.radii, max / self.radii);
+        let smallest = self.radii.x.min(self.radii.y).min(self.radii.z);
+        let largest = self.radii.x.max(self.radii.y).max(self.radii.z);
+        let lower = k0.min - 1.0;
+        let upper = k0.max - 1.0;
+        Interval::new(
+            lower * if lower < 0.0 { largest } else { smallest },
+            upper * if upper > 0.0 { largest } else { smallest },
+        )
+    }
+}
+
+/// The union of two sources (i.e. CSG union operation)
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Union<A, B> {
+    /// One of the sources to combine
+    pub a: A,
+    /// The other source to combine
+    pub b: B,
+}
+
+impl<A: Source, B: Source> Union<A, B> {
+    /// Create the union of two sources
+    pub fn new(a: A, b: B) -> Self {
+        Self { a, b }
+    }
+}
+
+impl<A: Source, B: Source> Source for Union<A, B> {
+    fn sample(&self, x: f32, y: f32, z: f32) -> f32 {
+        self.a.sample(x, y, z).min(self.b.sample(x, y, z))
+    }
+}
+
+impl<A: HermiteSource, B: HermiteSource> HermiteSource for Union<A, B> {
+    fn sample_normal(&self, x: f32, y: f32, z: f32) -> Vec3 {
+        let a = hermite(&self.a, x, y, z);
+        let b = hermite(&self.b, x, y, z);
+        a.min(b).gradient
+    }
+}
+
+impl<A: Field, B: Field> Field for Union<A, B> {
+    fn evaluate<T: Scalar>(&self, x: T, y: T, z: T) -> T {
+        let a = self.a.evaluate(x, y, z);
+        let b = self.b.evaluate(x, y, z);
+        a.min(b)
+    }
+}
+
+impl<A: BoundedSource, B: BoundedSource> BoundedSource for Union<A, B> {
+    fn sample_interval(&self, min: Vec3, max: Vec3) -> Interval {
+        self.a
+            .sample_interval(min, max)
+            .min(self.b.sample_interval(min, max))
+    }
+}
+
+/// The intersection of two sources (i.e. CSG intersection operation)
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Intersection<A, B> {
+    /// One of the sources to intersect
+    pub a: A,
+    /// The other source to intersect
+    pub b: B,
+}
+
+impl<A: Source, B: Source> Intersection<A, B> {
+    /// Create the intersection of two sources
+    pub fn new(a: A, b: B) -> Self {
+        Self { a, b }
+    }
+}
+
+impl<A: Source, B: Source> Source for Intersection<A, B> {
+    fn sample(&self, x: f32, y: f32, z: f32) -> f32 {
+        self.a.sample(x, y, z).max(self.b.sample(x, y, z))
+    }
+}
+
+impl<A: HermiteSource, B: HermiteSource> HermiteSource for Intersection<A, B> {
+    fn sample_normal(&self, x: f32, y: f32, z: f32) -> Vec3 {
+        let a = hermite(&self.a, x, y, z);
+        let b = hermite(&self.b, x, y, z);
+        a.max(b).gradient
+    }
+}
+
+impl<A: Field, B: Field> Field for Intersection<A, B> {
+    fn evaluate<T: Scalar>(&self, x: T, y: T, z: T) -> T {
+        let a = self.a.evaluate(x, y, z);
+        let b = self.b.evaluate(x, y, z);
+        a.max(b)
+    }
+}
+
+impl<A: BoundedSource, B: BoundedSource> BoundedSource for Intersection<A, B> {
+    fn sample_interval(&self, min: Vec3, max: Vec3) -> Interval {
+        self.a
+            .sample_interval(min, max)
+            .max(self.b.sample_interval(min, max))
+    }
+}
+
+/// One source with another subtracted from it (i.e. CSG difference operation)
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Difference<A, B> {
+    /// The source to subtract from
+    pub a: A,
+    /// The source subtracted
+    pub b: B,
+}
+
+impl<A: Source, B: Source> Difference<A, B> {
+    /// Create a source which subtracts `b` from `a`
+    pub fn new(a: A, b: B) -> Self {
+        Self { a, b }
+    }
+}
+
+impl<A: Source, B: Source> Source for Difference<A, B> {
+    fn sample(&self, x: f32, y: f32, z: f32) -> f32 {
+        self.a.sample(x, y, z).max(-self.b.sample(x, y, z))
+    }
+}
+
+impl<A: HermiteSource, B: HermiteSource> HermiteSource for Difference<A, B> {
+    fn sample_normal(&self, x: f32, y: f32, z: f32) -> Vec3 {
+        let a = hermite(&self.a, x, y, z);
+        let b = hermite(&self.b, x, y, z);
+        a.max(-b).gradient
+    }
+}
+
+impl<A: Field, B: Field> Field for Difference<A, B> {
+    fn evaluate<T: Scalar>(&self, x: T, y: T, z: T) -> T {
+        let a = self.a.evaluate(x, y, z);
+        let b = self.b.evaluate(x, y, z);
+        a.max(-b)
+    }
+}
+
+impl<A: BoundedSource, B: BoundedSource> BoundedSource for Difference<A, B> {
+    fn sample_interval(&self, min: Vec3, max: Vec3) -> Interval {
+        self.a
+            .sample_interval(min, max)
+            .max(-self.b.sample_interval(min, max))
+    }
+}
+
+/// How the smooth CSG operations blend two surfaces together
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Blend {
+    /// Blend with a quadratic polynomial, which only affects points within the given distance of
+    /// both surfaces.
+    Polynomial(f32),
+    /// Blend exponentially, over roughly the given distance. Produces a smoother result than the
+    /// polynomial blend, but affects every point to some degree.
+    Exponential(f32),
+}
+
+impl Blend {
+    /// The smooth minimum of two distances
+    pub fn min<T: Scalar>(&self, a: T, b: T) -> T {
+        match *self {
+            Blend::Polynomial(k) => {
+                if k <= 0.0 {
+                    return a.min(b);
+                }
+                let h = (-(a - b).abs() + k).max(T::constant(0.0)) / k;
+                a.min(b) - h * h * k * 0.25
+            }
+            Blend::Exponential(k) => {
+                if k <= 0.0 {
+                    return a.min(b);
+                }
+                // Factored out the minimum to avoid overflow far from the surface
+                a.min(b) - (-(a - b).abs() / k).exp().ln_1p() * k
+            }
+        }
+    }
+
+    /// The smooth maximum of two distances
+    pub fn max<T: Scalar>(&self, a: T, b: T) -> T {
+        -self.min(-a, -b)
+    }
+
+    // Bounds the smooth minimum of any pair of distances from two intervals. The blend only ever
+    // lowers the minimum, and by no more than a fixed amount.
+    fn min_interval(&self, a: Interval, b: Interval) -> Interval {
+        let depth = match *self {
+            Blend::Polynomial(k) => k.max(0.0) * 0.25,
+            Blend::Exponential(k) => k.max(0.0) * std::f32::consts::LN_2,
+        };
+        let interval = a.min(b);
+        Interval::new(interval.min - depth, interval.max)
+    }
+
+    // Bounds the smooth maximum of any pair of distances from two intervals
+    fn max_interval(&self, a: Interval, b: Interval) -> Interval {
+        -self.min_interval(-a, -b)
+    }
+}
+
+/// The union of two sources, with the seam between them smoothly blended
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SmoothUnion<A, B> {
+    /// One of the sources to combine
+    pub a: A,
+    /// The other source to combine
+    pub b: B,
+    /// How the seam between them is blended
+    pub blend: Blend,
+}
+
+impl<A: Source, B: Source> SmoothUnion<A, B> {
+    /// Create the smooth union of two sources
+    pub fn new(a: A, b: B, blend: Blend) -> Self {
+        Self { a, b, blend }
+    }
+}
+
+impl<A: Source, B: Source> Source for SmoothUnion<A, B> {
+    fn sample(&self, x: f32, y: f32, z: f32) -> f32 {
+        self.blend
+            .min(self.a.sample(x, y, z), self.b.sample(x, y, z))
+    }
+}
+
+impl<A: HermiteSource, B: HermiteSource> HermiteSource for SmoothUnion<A, B> {
+    fn sample_normal(&self, x: f32, y: f32, z: f32) -> Vec3 {
+        let a = hermite(&self.a, x, y, z);
+        let b = hermite(&self.b, x, y, z);
+        self.blend.min(a, b).gradient
+    }
+}
+
+impl<A: Field, B: Field> Field for SmoothUnion<A, B> {
+    fn evaluate<T: Scalar>(&self, x: T, y: T, z: T) -> T {
+        let a = self.a.evaluate(x, y, z);
+        let b = self.b.evaluate(x, y, z);
+        self.blend.min(a, b)
+    }
+}
+
+impl<A: BoundedSource, B: BoundedSource> BoundedSource for SmoothUnion<A, B> {
+    fn sample_interval(&self, min: Vec3, max: Vec3) -> Interval {
+        self.blend.min_interval(
+            self.a.sample_interval(min, max),
+            self.b.sample_interval(min, max),
+        )
+    }
+}
+
+/// The intersection of two sources, with the seam between them smoothly blended
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SmoothIntersection<A, B> {
+    /// One of the sources to intersect
+    pub a: A,
+    /// The other source to intersect
+    pub b: B,
+    /// How the seam between them is blended
+    pub blend: Blend,
+}
+
+impl<A: Source, B: Source> SmoothIntersection<A, B> {
+    /// Create the smooth intersection of two sources
+    pub fn new(a: A, b: B, blend: Blend) -> Self {
+        Self { a, b, blend }
+    }
+}
+
+impl<A: Source, B: Source> Source for SmoothIntersection<A, B> {
+    fn sample(&self, x: f32, y: f32, z: f32) -> f32 {
+        self.blend
+            .max(self.a.sample(x, y, z), self.b.sample(x, y, z))
+    }
+}
+
+impl<A: HermiteSource, B: HermiteSource> HermiteSource for SmoothIntersection<A, B> {
+    fn sample_normal(&self, x: f32, y: f32, z: f32) -> Vec3 {
+        let a = hermite(&self.a, x, y, z);
+        let b = hermite(&self.b, x, y, z);
+        self.blend.max(a, b).gradient
+    }
+}
+
+impl<A: Field, B: Field> Field for SmoothIntersection<A, B> {
+    fn evaluate<T: Scalar>(&self, x: T, y: T, z: T) -> T {
+        let a = self.a.evaluate(x, y, z);
+        let b = self.b.evaluate(x, y, z);
+        self.blend.max(a, b)
+    }
+}
+
+impl<A: BoundedSource, B: BoundedSource> BoundedSource for SmoothIntersection<A, B> {
+    fn sample_interval(&self, min: Vec3, max: Vec3) -> Interval {
+        self.blend.max_interval(
+            self.a.sample_interval(min, max),
+            self.b.sample_interval(min, max),
+        )
+    }
+}
+
+/// One source with another subtracted from it, with the seam between them smoothly blended
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SmoothDifference<A, B> {
+    /// The source to subtract from
+    pub a: A,
+    /// The source subtracted
+    pub b: B,
+    /// How the seam between them is blended
+    pub blend: Blend,
+}
+
+impl<A: Source, B: Source> SmoothDifference<A, B> {
+    /// Create a source which smoothly subtracts `b` from `a`
+    pub fn new(a: A, b: B, blend: Blend) -> Self {
+        Self { a, b, blend }
+    }
+}
+
+impl<A: Source, B: Source> Source for SmoothDifference<A, B> {
+    fn sample(&self, x: f32, y: f32, z: f32) -> f32 {
+        self.blend
+            .max(self.a.sample(x, y, z), -self.b.sample(x, y, z))
+    }
+}
+
+impl<A: HermiteSource, B: HermiteSource> HermiteSource for SmoothDifference<A, B> {
+    fn sample_normal(&self, x: f32, y: f32, z: f32) -> Vec3 {
+        let a = hermite(&self.a, x, y, z);
+        let b = hermite(&self.b, x, y, z);
+        self.blend.max(a, -b).gradient
+    }
+}
+
+impl<A: Field, B: Field> Field for SmoothDifference<A, B> {
+    fn evaluate<T: Scalar>(&self, x: T, y: T, z: T) -> T {
+        let a = self.a.evaluate(x, y, z);
+        let b = self.b.evaluate(x, y, z);
+        self.blend.max(a, -b)
+    }
+}
+
+impl<A: BoundedSource, B: BoundedSource> BoundedSource for SmoothDifference<A, B> {
+    fn sample_interval(&self, min: Vec3, max: Vec3) -> Interval {
+        self.blend.max_interval(
+            self.a.sample_interval(min, max),
+            -self.b.sample_interval(min, max),
+        )
+    }
+}
+
+fn clamp<T: Scalar>(x: T, min: f32, max: f32) -> T {
+    x.max(T::constant(min)).min(T::constant(max))
+}
+
+fn cuboid<T: Scalar>(x: T, y: T, z: T, half_extents: Vec3) -> T {
+    let zero = T::constant(0.0);
+    let dx = x.abs() - half_extents.x;
+    let dy = y.abs() - half_extents.y;
+    let dz = z.abs() - half_extents.z;
+    let (ox, oy, oz) = (dx.max(zero), dy.max(zero), dz.max(zero));
+    let outside = (ox * ox + oy * oy + oz * oz).sqrt();
+    dx.max(dy.max(dz)).min(zero) + outside
+}
+
+// The exact gradient of a field, from a single evaluation with dual numbers
+fn gradient<F: Field>(field: &F, x: f32, y: f32, z: f32) -> Vec3 {
+    let (x, y, z) = Dual::position(x, y, z);
+    field.evaluate(x, y, z).gradient
+}
+
+// The value and normal of a source as a dual number, so that the normals of two sources can be
+// combined by the chain rule
+fn hermite<S: HermiteSource>(source: &S, x: f32, y: f32, z: f32) -> Dual {
+    Dual::new(source.sample(x, y, z), source.sample_normal(x, y, z))
+}
+
+// Bounds an exact distance field over a box, from its value at the centre of the box, as no
+// point in the box lies further than half its diagonal from the centre
+fn lipschitz_interval<S: Source>(source: &S, min: Vec3, max: Vec3) -> Interval {
+    let centre = (min + max) * 0.5;
+    let radius = (max - min).length() * 0.5;
+    let value = source.sample(centre.x, centre.y, centre.z);
+    Interval::new(value - radius, value + radius)
+}
+
+// The range of distances from the origin to the points of a box
+fn distance_interval(min: Vec3, max: Vec3) -> Interval {
+    let mut nearest = Vec3::zero();
+    let mut furthest = Vec3::zero();
+    for axis in 0..3 {
+        nearest[axis] = min[axis].max(-max[axis]).max(0.0);
+        furthest[axis] = min[axis].abs().max(max[axis].abs());
+    }
+    Interval::new(nearest.length(), furthest.length())
+}
@@ -0,0 +1,67 @@
+// Copyright 2018 Tristam MacDonald
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use math::Vec3;
+
+/// A sink capable of receiving the vertices and triangles of an extracted mesh.
+///
+/// Implement this to write extracted meshes straight into your own vertex formats, index
+/// formats or buffers, without first collecting them into intermediate vectors.
+pub trait MeshSink {
+    /// Adds a vertex to the mesh.
+    ///
+    /// Vertices are implicitly numbered in the order they are added, starting from zero for each
+    /// extraction. The `normal` is only provided when extracting from a
+    /// [HermiteSource](../source/trait.HermiteSource.html) with normals.
+    fn add_vertex(&mut self, position: Vec3, normal: Option<Vec3>);
+
+    /// Adds a triangle to the mesh, given the indices of its three vertices.
+    fn add_triangle(&mut self, a: u32, b: u32, c: u32);
+}
+
+/// Appends extracted meshes to vectors of interleaved vertex data and indices.
+///
+/// Vertices will be appended to `vertices` as triples of (x, y, z) coordinates, each followed by
+/// the surface normal as a triple of (x, y, z) dimensions if normals are being extracted.
+/// Triangles will be appended to `indices` as triples of vertex indices.
+pub struct VecSink<'a> {
+    vertices: &'a mut Vec<f32>,
+    indices: &'a mut Vec<u32>,
+}
+
+impl<'a> VecSink<'a> {
+    /// Create a sink appending to the given vectors
+    pub fn new(vertices: &'a mut Vec<f32>, indices: &'a mut Vec<u32>) -> VecSink<'a> {
+        VecSink { vertices, indices }
+    }
+}
+
+impl<'a> MeshSink for VecSink<'a> {
+    fn add_vertex(&mut self, position: Vec3, normal: Option<Vec3>) {
+        self.vertices.push(position.x);
+        self.vertices.push(position.y);
+        self.vertices.push(position.z);
+        if let Some(n) = normal {
+            self.vertices.push(n.x);
+            self.vertices.push(n.y);
+            self.vertices.push(n.z);
+        }
+    }
+
+    fn add_triangle(&mut self, a: u32, b: u32, c: u32) {
+        self.indices.push(a);
+        self.indices.push(b);
+        self.indices.push(c);
+    }
+}
@@ -12,7 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use math::Vec3;
+use math::{Dual, Interval, Scalar, Vec3};
+use region::Region;
 
 /// A source capable of sampling a signed distance field at discrete coordinates.
 pub trait Source {
@@ -21,6 +22,50 @@ pub trait Source {
     /// Must return the signed distance (i.e. negative for coodinates inside the surface),
     /// as our Marching Cubes implementation will evaluate the surface at the zero-crossing.
     fn sample(&self, x: f32, y: f32, z: f32) -> f32;
+
+    /// Samples a whole layer of the given [`Region`](../region/struct.Region.html) at once, at
+    /// every point with the z index `z`.
+    ///
+    /// Values are written to the start of `values` in x, then y order, which must hold at least
+    /// as many values as there are points in the layer. The grid extractors fill each layer with
+    /// a single call to this, so sources which can sample many points faster than one at a time,
+    /// such as with SIMD, or by amortising the overhead of each call, may override it. The
+    /// default implementation calls [`sample`](#tymethod.sample) at each point.
+    fn sample_layer(&self, region: &Region, z: usize, values: &mut [f32]) {
+        let [size_x, size_y, _] = region.samples;
+        let origin = region.origin;
+        let step = region.step();
+
+        for y in 0..size_y {
+            for x in 0..size_x {
+                values[y * size_x + x] = self.sample(
+                    origin.x + x as f32 * step.x,
+                    origin.y + y as f32 * step.y,
+                    origin.z + z as f32 * step.z,
+                );
+            }
+        }
+    }
+}
+
+impl<S: Source + ?Sized> Source for &S {
+    fn sample(&self, x: f32, y: f32, z: f32) -> f32 {
+        (**self).sample(x, y, z)
+    }
+
+    fn sample_layer(&self, region: &Region, z: usize, values: &mut [f32]) {
+        (**self).sample_layer(region, z, values)
+    }
+}
+
+impl<S: Source + ?Sized> Source for Box<S> {
+    fn sample(&self, x: f32, y: f32, z: f32) -> f32 {
+        (**self).sample(x, y, z)
+    }
+
+    fn sample_layer(&self, region: &Region, z: usize, values: &mut [f32]) {
+        (**self).sample_layer(region, z, values)
+    }
 }
 
 /// A source capable of evaluating the normal vector to a signed distance field at discrete coordinates.
@@ -31,40 +76,244 @@ pub trait HermiteSource: Source {
     fn sample_normal(&self, x: f32, y: f32, z: f32) -> Vec3;
 }
 
-/// Adapts a `Source` to a `HermiteSource` by deriving normals from the surface via central differencing
-pub struct CentralDifference {
-    source: Box<Source>,
+impl<S: HermiteSource + ?Sized> HermiteSource for &S {
+    fn sample_normal(&self, x: f32, y: f32, z: f32) -> Vec3 {
+        (**self).sample_normal(x, y, z)
+    }
+}
+
+impl<S: HermiteSource + ?Sized> HermiteSource for Box<S> {
+    fn sample_normal(&self, x: f32, y: f32, z: f32) -> Vec3 {
+        (**self).sample_normal(x, y, z)
+    }
+}
+
+/// A source capable of bounding the values of a distance field over a box.
+///
+/// Extractors use the bounds to skip the parts of the volume which the surface can't pass through.
+pub trait BoundedSource: Source {
+    /// Returns an interval containing every value of the distance field within the axis-aligned
+    /// box from `min` to `max`.
+    ///
+    /// The interval may be larger than the true range of values, but must never be smaller.
+    fn sample_interval(&self, min: Vec3, max: Vec3) -> Interval;
+}
+
+impl<S: BoundedSource + ?Sized> BoundedSource for &S {
+    fn sample_interval(&self, min: Vec3, max: Vec3) -> Interval {
+        (**self).sample_interval(min, max)
+    }
+}
+
+impl<S: BoundedSource + ?Sized> BoundedSource for Box<S> {
+    fn sample_interval(&self, min: Vec3, max: Vec3) -> Interval {
+        (**self).sample_interval(min, max)
+    }
+}
+
+/// A distance field, written once over any [`Scalar`](../math/trait.Scalar.html) type.
+///
+/// Evaluated with `f32` the field produces its value, and evaluated with
+/// [`Dual`](../math/struct.Dual.html) numbers it produces its exact gradient as well, so a field
+/// can be adapted to both a `Source` and a `HermiteSource` with [`AutoDiff`](struct.AutoDiff.html).
+/// The primitives and operations in the [sdf](../sdf/index.html) module all implement `Field`, and
+/// may be evaluated within other fields.
+pub trait Field {
+    /// Evaluates the distance field at the given (x, y, z) coordinates.
+    ///
+    /// Any branches should be taken on the [`value`](../math/trait.Scalar.html#tymethod.value) of
+    /// the coordinates, so that every scalar type follows the same path.
+    fn evaluate<T: Scalar>(&self, x: T, y: T, z: T) -> T;
+}
+
+impl<F: Field + ?Sized> Field for &F {
+    fn evaluate<T: Scalar>(&self, x: T, y: T, z: T) -> T {
+        (**self).evaluate(x, y, z)
+    }
+}
+
+impl<F: Field + ?Sized> Field for Box<F> {
+    fn evaluate<T: Scalar>(&self, x: T, y: T, z: T) -> T {
+        (**self).evaluate(x, y, z)
+    }
+}
+
+/// Adapts a `Field` to a `HermiteSource`, with normals from the exact gradient of the field
+///
+/// The gradient is found with automatic differentiation, by evaluating the field once with
+/// [`Dual`](../math/struct.Dual.html) numbers, so unlike
+/// [`CentralDifference`](struct.CentralDifference.html) it needs no extra samples, and no epsilon.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct AutoDiff<F> {
+    /// The field sampled, and differentiated
+    pub field: F,
+}
+
+impl<F: Field> AutoDiff<F> {
+    /// Create an adaptor from a [Field](trait.Field.html)
+    pub fn new(field: F) -> Self {
+        Self { field }
+    }
+}
+
+impl<F: Field> Source for AutoDiff<F> {
+    fn sample(&self, x: f32, y: f32, z: f32) -> f32 {
+        self.field.evaluate(x, y, z)
+    }
+}
+
+impl<F: Field> HermiteSource for AutoDiff<F> {
+    fn sample_normal(&self, x: f32, y: f32, z: f32) -> Vec3 {
+        let (x, y, z) = Dual::position(x, y, z);
+        self.field.evaluate(x, y, z).gradient
+    }
+}
+
+impl<F: Field + BoundedSource> BoundedSource for AutoDiff<F> {
+    fn sample_interval(&self, min: Vec3, max: Vec3) -> Interval {
+        self.field.sample_interval(min, max)
+    }
+}
+
+/// The pattern of samples [`CentralDifference`](struct.CentralDifference.html) takes around each
+/// point to estimate the gradient
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Stencil {
+    /// One-sided differences along each axis, from 4 samples. The cheapest, but only first order
+    /// accurate, and biased half an epsilon towards the positive axes.
+    Forward,
+    /// Differences either side of the point along each axis, from 6 samples. Second order
+    /// accurate, and symmetric about the point.
+    Central,
+    /// Differences between the 4 corners of a tetrahedron centred on the point, from 4 samples.
+    /// Costs the same as `Forward`, and isn't biased along the axes, but is still only first
+    /// order accurate where the surface curves.
+    Tetrahedral,
+}
+
+/// Adapts a `Source` to a `HermiteSource` by deriving normals from the surface via finite differences
+///
+/// The source may be owned, borrowed or boxed. Normals are an estimate of the gradient of the
+/// source, unless normalisation is enabled with [`set_normalize`](#method.set_normalize).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CentralDifference<S> {
+    source: S,
     epsilon: f32,
+    stencil: Stencil,
+    normalize: bool,
 }
 
-impl CentralDifference {
+impl<S: Source> CentralDifference<S> {
     /// Create an adaptor from a [Source](trait.Source.html)
-    pub fn new(source: Box<Source>) -> CentralDifference {
+    pub fn new(source: S) -> CentralDifference<S> {
+        CentralDifference::new_with_epsilon(source, 0.0001)
+    }
+
+    /// Create an adaptor from a [Source](trait.Source.html) and an epsilon value
+    pub fn new_with_epsilon(source: S, epsilon: f32) -> CentralDifference<S> {
         CentralDifference {
             source,
-            epsilon: 0.0001,
+            epsilon,
+            stencil: Stencil::Central,
+            normalize: false,
         }
     }
 
-    /// Create an adaptor from a [Source](trait.Source.html) and an epsilon value
-    pub fn new_with_epsilon(source: Box<Source>, epsilon: f32) -> CentralDifference {
-        CentralDifference { source, epsilon }
+    /// Change the pattern of samples used to estimate the gradient. Defaults to `Stencil::Central`.
+    pub fn set_stencil(&mut self, stencil: Stencil) {
+        self.stencil = stencil;
+    }
+
+    /// The pattern of samples used to estimate the gradient
+    pub fn stencil(&self) -> Stencil {
+        self.stencil
+    }
+
+    /// Whether normals are scaled to unit length, rather than returned as the estimated gradient.
+    /// Defaults to false.
+    pub fn set_normalize(&mut self, normalize: bool) {
+        self.normalize = normalize;
+    }
+
+    /// Whether normals are scaled to unit length
+    pub fn normalize(&self) -> bool {
+        self.normalize
+    }
+
+    /// The distance between samples
+    pub fn epsilon(&self) -> f32 {
+        self.epsilon
+    }
+
+    /// The adapted source
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+
+    /// Unwrap the adapted source
+    pub fn into_inner(self) -> S {
+        self.source
+    }
+
+    fn gradient(&self, x: f32, y: f32, z: f32) -> Vec3 {
+        let e = self.epsilon;
+        let s = &self.source;
+
+        match self.stencil {
+            Stencil::Forward => {
+                let v = s.sample(x, y, z);
+                Vec3::new(
+                    s.sample(x + e, y, z) - v,
+                    s.sample(x, y + e, z) - v,
+                    s.sample(x, y, z + e) - v,
+                ) * (1.0 / e)
+            }
+            Stencil::Central => {
+                Vec3::new(
+                    s.sample(x + e, y, z) - s.sample(x - e, y, z),
+                    s.sample(x, y + e, z) - s.sample(x, y - e, z),
+                    s.sample(x, y, z + e) - s.sample(x, y, z - e),
+                ) * (0.5 / e)
+            }
+            Stencil::Tetrahedral => {
+                // The corners (1, -1, -1), (-1, -1, 1), (-1, 1, -1) and (1, 1, 1) of a cube,
+                // scaled by epsilon. Their outer products sum to 4 times the identity, so the sum
+                // of each corner weighted by its sample is 4 epsilon times the gradient, to first order.
+                let a = s.sample(x + e, y - e, z - e);
+                let b = s.sample(x - e, y - e, z + e);
+                let c = s.sample(x - e, y + e, z - e);
+                let d = s.sample(x + e, y + e, z + e);
+
+                Vec3::new(a - b - c + d, -a - b + c + d, -a + b - c + d) * (0.25 / e)
+            }
+        }
     }
 }
 
-impl Source for CentralDifference {
+impl<S: Source> Source for CentralDifference<S> {
     fn sample(&self, x: f32, y: f32, z: f32) -> f32 {
         self.source.sample(x, y, z)
     }
+
+    fn sample_layer(&self, region: &Region, z: usize, values: &mut [f32]) {
+        self.source.sample_layer(region, z, values)
+    }
 }
 
-impl HermiteSource for CentralDifference {
+impl<S: Source> HermiteSource for CentralDifference<S> {
     fn sample_normal(&self, x: f32, y: f32, z: f32) -> Vec3 {
-        let v = self.sample(x, y, z);
-        let vx = self.sample(x + self.epsilon, y, z);
-        let vy = self.sample(x, y + self.epsilon, z);
-        let vz = self.sample(x, y, z + self.epsilon);
+        let gradient = self.gradient(x, y, z);
+
+        if self.normalize {
+            gradient.normalize()
+        } else {
+            gradient
+        }
+    }
+}
 
-        Vec3::new(vx - v, vy - v, vz - v)
+impl<S: BoundedSource> BoundedSource for CentralDifference<S> {
+    fn sample_interval(&self, min: Vec3, max: Vec3) -> Interval {
+        self.source.sample_interval(min, max)
     }
 }
@@ -0,0 +1,80 @@
+// Copyright 2018 Tristam MacDonald
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use index_cache::EMPTY;
+use marching_cubes_tables::CORNERS;
+
+/// Tracks vertex indices to avoid emitting duplicate vertices during marching tetrahedra mesh
+/// generation
+///
+/// Besides the edges of the sampling grid, tetrahedra use the diagonals of each face and of each
+/// cell, all of which run from their lowest corner towards positive x, y and z. Each is cached
+/// against its lowest corner and direction, in exactly one place.
+pub struct TetrahedronIndexCache {
+    size_x: usize,
+    // The x, y and xy aligned edges of the planes at the bottom and top of the current layer
+    planes: [Vec<[u32; 3]>; 2],
+    // The z, xz, yz and xyz aligned edges crossing the current layer
+    verticals: Vec<[u32; 4]>,
+}
+
+impl TetrahedronIndexCache {
+    /// Create a new TetrahedronIndexCache for a chunk with the given number of samples along x
+    /// and y
+    pub fn new(size_x: usize, size_y: usize) -> TetrahedronIndexCache {
+        TetrahedronIndexCache {
+            size_x,
+            planes: [
+                vec![[EMPTY; 3]; size_x * size_y],
+                vec![[EMPTY; 3]; size_x * size_y],
+            ],
+            verticals: vec![[EMPTY; 4]; size_x * size_y],
+        }
+    }
+
+    /// Put an index in the cache for the edge between corners `u` and `v` of cell (x, y), where
+    /// `u` is the lower corner
+    pub fn put(&mut self, x: usize, y: usize, u: usize, v: usize, index: u32) {
+        *self.slot(x, y, u, v) = index;
+    }
+
+    /// Retrieve an index from the cache for the edge between corners `u` and `v` of cell (x, y),
+    /// where `u` is the lower corner, or `EMPTY`
+    pub fn get(&mut self, x: usize, y: usize, u: usize, v: usize) -> u32 {
+        *self.slot(x, y, u, v)
+    }
+
+    /// Update the cache when mesh extraction moves to the next layer
+    pub fn advance_layer(&mut self) {
+        self.planes.swap(0, 1);
+        for i in &mut self.planes[1] {
+            *i = [EMPTY; 3];
+        }
+        for i in &mut self.verticals {
+            *i = [EMPTY; 4];
+        }
+    }
+
+    fn slot(&mut self, x: usize, y: usize, u: usize, v: usize) -> &mut u32 {
+        let [ux, uy, uz] = CORNERS[u];
+        let direction =
+            (CORNERS[v][0] - ux) | (CORNERS[v][1] - uy) << 1 | (CORNERS[v][2] - uz) << 2;
+        let i = (y + uy) * self.size_x + x + ux;
+        if direction < 4 {
+            &mut self.planes[uz][i][direction - 1]
+        } else {
+            &mut self.verticals[i][direction - 4]
+        }
+    }
+}
@@ -0,0 +1,321 @@
+// Copyright 2018 Tristam MacDonald
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use math::{Interval, Quat, Vec3};
+use source::{BoundedSource, HermiteSource, Source};
+
+/// Moves a source by the given offset
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Translate<S> {
+    /// The source to move
+    pub source: S,
+    /// How far to move the source along each axis
+    pub offset: Vec3,
+}
+
+impl<S: Source> Translate<S> {
+    /// Create an adaptor which moves the source by `offset`
+    pub fn new(source: S, offset: Vec3) -> Self {
+        Self { source, offset }
+    }
+}
+
+impl<S: Source> Source for Translate<S> {
+    fn sample(&self, x: f32, y: f32, z: f32) -> f32 {
+        self.source
+            .sample(x - self.offset.x, y - self.offset.y, z - self.offset.z)
+    }
+}
+
+impl<S: HermiteSource> HermiteSource for Translate<S> {
+    fn sample_normal(&self, x: f32, y: f32, z: f32) -> Vec3 {
+        self.source
+            .sample_normal(x - self.offset.x, y - self.offset.y, z - self.offset.z)
+    }
+}
+
+impl<S: BoundedSource> BoundedSource for Translate<S> {
+    fn sample_interval(&self, min: Vec3, max: Vec3) -> Interval {
+        self.source
+            .sample_interval(min - self.offset, max - self.offset)
+    }
+}
+
+/// Rotates a source around the origin
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Rotate<S> {
+    /// The source to rotate
+    pub source: S,
+    /// The rotation applied to the source, which should be a unit quaternion
+    pub rotation: Quat,
+}
+
+impl<S: Source> Rotate<S> {
+    /// Create an adaptor which rotates the source by the given quaternion.
+    ///
+    /// The quaternion is normalised, so that it represents a pure rotation.
+    pub fn new(source: S, rotation: Quat) -> Self {
+        Self {
+            source,
+            rotation: rotation.normalize(),
+        }
+    }
+
+    /// Create an adaptor which rotates the source by `angle` radians around `axis`
+    pub fn from_axis_angle(source: S, axis: Vec3, angle: f32) -> Self {
+        Self::new(source, Quat::from_axis_angle(axis, angle))
+    }
+
+    fn unrotate(&self, x: f32, y: f32, z: f32) -> Vec3 {
+        self.rotation.conjugate().rotate(Vec3::new(x, y, z))
+    }
+}
+
+impl<S: Source> Source for Rotate<S> {
+    fn sample(&self, x: f32, y: f32, z: f32) -> f32 {
+        let p = self.unrotate(x, y, z);
+        self.source.sample(p.x, p.y, p.z)
+    }
+}
+
+impl<S: HermiteSource> HermiteSource for Rotate<S> {
+    fn sample_normal(&self, x: f32, y: f32, z: f32) -> Vec3 {
+        let p = self.unrotate(x, y, z);
+        self.rotation
+            .rotate(self.source.sample_normal(p.x, p.y, p.z))
+    }
+}
+
+impl<S: BoundedSource> BoundedSource for Rotate<S> {
+    fn sample_interval(&self, min: Vec3, max: Vec3) -> Interval {
+        // Bound the source over a box enclosing the rotated box
+        let mut lower = Vec3::one() * f32::INFINITY;
+        let mut upper = Vec3::one() * f32::NEG_INFINITY;
+        for i in 0..8 {
+            let corner = self.unrotate(
+                if i & 1 == 0 { min.x } else { max.x },
+                if i & 2 == 0 { min.y } else { max.y },
+                if i & 4 == 0 { min.z } else { max.z },
+            );
+            for axis in 0..3 {
+                lower[axis] = lower[axis].min(corner[axis]);
+                upper[axis] = upper[axis].max(corner[axis]);
+            }
+        }
+        self.source.sample_interval(lower, upper)
+    }
+}
+
+/// Uniformly scales a source around the origin
+///
+/// Distances are scaled along with the source, so that a signed distance field remains a signed
+/// distance field.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Scale<S> {
+    /// The source to scale
+    pub source: S,
+    /// The uniform scale factor, which must be positive
+    pub scale: f32,
+}
+
+impl<S: Source> Scale<S> {
+    /// Create an adaptor which scales the source by `scale`, which must be positive
+    pub fn new(source: S, scale: f32) -> Self {
+        Self { source, scale }
+    }
+}
+
+impl<S: Source> Source for Scale<S> {
+    fn sample(&self, x: f32, y: f32, z: f32) -> f32 {
+        let s = self.scale;
+        self.source.sample(x / s, y / s, z / s) * s
+    }
+}
+
+impl<S: HermiteSource> HermiteSource for Scale<S> {
+    fn sample_normal(&self, x: f32, y: f32, z: f32) -> Vec3 {
+        let s = self.scale;
+        self.source.sample_normal(x / s, y / s, z / s)
+    }
+}
+
+impl<S: BoundedSource> BoundedSource for Scale<S> {
+    fn sample_interval(&self, min: Vec3, max: Vec3) -> Interval {
+        let s = Vec3::one() * self.scale;
+        self.source.sample_interval(min / s, max / s) * self.scale
+    }
+}
+
+/// Mirrors a source across the planes through the origin, perpendicular to the chosen axes
+///
+/// The half of the source on the positive side of each plane is reflected onto the negative side,
+/// replacing whatever was there, which results in a source symmetric about each of those planes.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Mirror<S> {
+    /// The source to mirror
+    pub source: S,
+    /// Whether to mirror across the plane perpendicular to the x, y and z axes respectively
+    pub axes: [bool; 3],
+}
+
+impl<S: Source> Mirror<S> {
+    /// Create an adaptor which mirrors the source along the chosen axes
+    pub fn new(source: S, axes: [bool; 3]) -> Self {
+        Self { source, axes }
+    }
+
+    fn fold(&self, x: f32, y: f32, z: f32) -> Vec3 {
+        let mut p = Vec3::new(x, y, z);
+        for axis in 0..3 {
+            if self.axes[axis] {
+                p[axis] = p[axis].abs();
+            }
+        }
+        p
+    }
+}
+
+impl<S: Source> Source for Mirror<S> {
+    fn sample(&self, x: f32, y: f32, z: f32) -> f32 {
+        let p = self.fold(x, y, z);
+        self.source.sample(p.x, p.y, p.z)
+    }
+}
+
+impl<S: HermiteSource> HermiteSource for Mirror<S> {
+    fn sample_normal(&self, x: f32, y: f32, z: f32) -> Vec3 {
+        let p = self.fold(x, y, z);
+        let mut normal = self.source.sample_normal(p.x, p.y, p.z);
+        let original = [x, y, z];
+        for axis in 0..3 {
+            if self.axes[axis] && original[axis] < 0.0 {
+                normal[axis] = -normal[axis];
+            }
+        }
+        normal
+    }
+}
+
+impl<S: BoundedSource> BoundedSource for Mirror<S> {
+    fn sample_interval(&self, min: Vec3, max: Vec3) -> Interval {
+        let mut lower = min;
+        let mut upper = max;
+        for axis in 0..3 {
+            if self.axes[axis] {
+                // Boxes straddling the plane fold over onto themselves
+                lower[axis] = min[axis].max(-max[axis]).max(0.0);
+                upper[axis] = min[axis].abs().max(max[axis].abs());
+            }
+        }
+        self.source.sample_interval(lower, upper)
+    }
+}
+
+/// Repeats a source at regular intervals
+///
+/// The source should fit within one period, centred on the origin, or the copies will be clipped
+/// where they meet.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Repeat<S> {
+    /// The source to repeat
+    pub source: S,
+    /// The distance between copies along each axis. Axes with a period of zero are not repeated.
+    pub period: Vec3,
+    /// The number of copies either side of the original along each axis, or `None` to repeat
+    /// indefinitely.
+    pub limit: Option<[u32; 3]>,
+}
+
+impl<S: Source> Repeat<S> {
+    /// Create an adaptor which repeats the source indefinitely, every `period` units along each
+    /// axis
+    pub fn new(source: S, period: Vec3) -> Self {
+        Self {
+            source,
+            period,
+            limit: None,
+        }
+    }
+
+    /// Create an adaptor which repeats the source every `period` units along each axis, with
+    /// `limit` copies either side of the original
+    pub fn finite(source: S, period: Vec3, limit: [u32; 3]) -> Self {
+        Self {
+            source,
+            period,
+            limit: Some(limit),
+        }
+    }
+
+    fn fold(&self, x: f32, y: f32, z: f32) -> Vec3 {
+        let mut p = Vec3::new(x, y, z);
+        for axis in 0..3 {
+            let period = self.period[axis];
+            if period <= 0.0 {
+                continue;
+            }
+
+            p[axis] -= self.cell(axis, p[axis]) * period;
+        }
+        p
+    }
+
+    // The index of the copy which the given coordinate along an axis falls within
+    fn cell(&self, axis: usize, coordinate: f32) -> f32 {
+        let mut cell = (coordinate / self.period[axis]).round();
+        if let Some(limit) = self.limit {
+            let limit = limit[axis] as f32;
+            cell = cell.max(-limit).min(limit);
+        }
+        cell
+    }
+}
+
+impl<S: Source> Source for Repeat<S> {
+    fn sample(&self, x: f32, y: f32, z: f32) -> f32 {
+        let p = self.fold(x, y, z);
+        self.source.sample(p.x, p.y, p.z)
+    }
+}
+
+impl<S: HermiteSource> HermiteSource for Repeat<S> {
+    fn sample_normal(&self, x: f32, y: f32, z: f32) -> Vec3 {
+        let p = self.fold(x, y, z);
+        self.source.sample_normal(p.x, p.y, p.z)
+    }
+}
+
+impl<S: BoundedSource> BoundedSource for Repeat<S> {
+    fn sample_interval(&self, min: Vec3, max: Vec3) -> Interval {
+        let mut folded_min = min;
+        let mut folded_max = max;
+        for axis in 0..3 {
+            let period = self.period[axis];
+            if period <= 0.0 {
+                continue;
+            }
+
+            let lower = self.cell(axis, min[axis]);
+            let upper = self.cell(axis, max[axis]);
+            folded_min[axis] -= lower * period;
+            folded_max[axis] -= upper * period;
+            // Boxes spanning several copies cover at least one whole period of the source
+            if lower != upper {
+                folded_min[axis] = folded_min[axis].min(-0.5 * period);
+                folded_max[axis] = folded_max[axis].max(0.5 * period);
+            }
+        }
+        self.source.sample_interval(folded_min, folded_max)
+    }
+}
@@ -0,0 +1,293 @@
+// Copyright 2018 Tristam MacDonald
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use marching_cubes::Faces;
+use marching_cubes_impl::edge_vertex;
+use math::Vec3;
+use region::Region;
+use sink::MeshSink;
+use source::Source;
+use std::collections::HashMap;
+use transvoxel_tables::{TRANSITION_EDGES, TRANSITION_SAMPLES, TRANSITION_TRIANGLES};
+
+// The fraction of a cell that transition cells occupy. Regular cells along a transition face are
+// squashed by this amount to make room.
+const TRANSITION_WIDTH: f32 = 0.5;
+
+// Keys edges on the high-resolution face of transition cells, which are shared with regular cells.
+const HIGH_RESOLUTION: usize = 6;
+
+// Uniquely identifies an edge by the face it lies on, and its terminal grid coordinates
+type SeamEdge = (usize, [usize; 3], [usize; 3]);
+
+// The samples of a single transition cell
+struct TransitionCell {
+    // The face of the chunk the cell lies on
+    face: usize,
+    // The grid coordinates of each sample
+    samples: [[usize; 3]; 13],
+    values: [f32; 13],
+}
+
+/// Generates the transition cells that stitch a chunk to half-resolution neighbours.
+///
+/// Transition cells are laid out as described in Eric Lengyel's
+/// [Transvoxel Algorithm](http://transvoxel.org/), but triangulated with a generated table rather
+/// than Lengyel's. Each one spans 2x2 cells of the chunk face, with a high-resolution face of 9
+/// samples pushed slightly inside the chunk, and a low-resolution face of 4 samples on the chunk
+/// boundary, which matches the face of the neighbouring cell at half resolution.
+pub struct Transitions {
+    faces: Faces,
+    region: Region,
+    iso_level: f32,
+    step: Vec3,
+    upper: Vec3,
+    seam: HashMap<SeamEdge, u32>,
+}
+
+impl Transitions {
+    /// Create the transitions for the given set of faces, on a chunk sampling the given region,
+    /// with the surface at the given iso-level
+    pub fn new(faces: Faces, region: Region, iso_level: f32) -> Transitions {
+        let [size_x, size_y, size_z] = region.samples;
+        Transitions {
+            faces,
+            region,
+            iso_level,
+            step: region.step(),
+            upper: region.position(size_x - 1, size_y - 1, size_z - 1),
+            seam: HashMap::new(),
+        }
+    }
+
+    /// Move a vertex of a regular cell to make room for any adjacent transition cells
+    ///
+    /// Vertices are only moved along the normals of transition faces. Vertices on any other face
+    /// of the chunk are shared with a neighbour which knows nothing of the transition cells, so
+    /// they stay where they are.
+    pub fn shrink(&self, vertex: Vec3) -> Vec3 {
+        if self.faces.is_empty() {
+            return vertex;
+        }
+
+        for axis in 0..3 {
+            let on_lower = vertex[axis] == self.region.origin[axis];
+            let on_upper = vertex[axis] == self.upper[axis];
+            if (on_lower && !self.faces.contains(Faces::negative(axis)))
+                || (on_upper && !self.faces.contains(Faces::positive(axis)))
+            {
+                return vertex;
+            }
+        }
+
+        let mut result = vertex;
+        for axis in 0..3 {
+            let lower = self.region.origin[axis];
+            let upper = self.upper[axis];
+            let step = self.step[axis];
+            let width = step * TRANSITION_WIDTH;
+
+            let c = vertex[axis];
+            if c < lower + step && self.faces.contains(Faces::negative(axis)) {
+                result[axis] = lower + width + (c - lower) * (1.0 - TRANSITION_WIDTH);
+            } else if c > upper - step && self.faces.contains(Faces::positive(axis)) {
+                result[axis] = upper - width - (upper - c) * (1.0 - TRANSITION_WIDTH);
+            }
+        }
+        result
+    }
+
+    /// Record the index of a regular cell vertex lying on an edge between the given grid
+    /// coordinates, so that transition cells can share it.
+    pub fn put(&mut self, a: [usize; 3], b: [usize; 3], index: u32) {
+        if self.faces.is_empty() {
+            return;
+        }
+
+        for axis in 0..3 {
+            let on_face = (a[axis] == 0 && self.faces.contains(Faces::negative(axis)))
+                || (a[axis] == self.region.samples[axis] - 1
+                    && self.faces.contains(Faces::positive(axis)));
+            if on_face && a[axis] == b[axis] {
+                self.seam.insert(seam_edge(HIGH_RESOLUTION, a, b), index);
+                return;
+            }
+        }
+    }
+
+    /// Extract the transition cells on each face
+    ///
+    /// Each new vertex is assigned the next available value of `index`, and its normal is
+    /// provided by the `normal` function.
+    pub fn extract<S, N, M>(&mut self, source: &S, index: &mut u32, normal: &N, sink: &mut M)
+    where
+        S: Source,
+        N: Fn(Vec3) -> Option<Vec3>,
+        M: MeshSink,
+    {
+        for face in 0..6 {
+            let axis = face / 2;
+            let positive = face % 2 == 1;
+            let face_set = if positive {
+                Faces::positive(axis)
+            } else {
+                Faces::negative(axis)
+            };
+            if !self.faces.contains(face_set) {
+                continue;
+            }
+
+            // The u and v axes of the face, which together with the outward normal form a
+            // right-handed basis when the face is positive.
+            let u_axis = (axis + 1) % 3;
+            let v_axis = (axis + 2) % 3;
+            let w = if positive {
+                self.region.samples[axis] - 1
+            } else {
+                0
+            };
+            let size_u = self.region.samples[u_axis];
+            let size_v = self.region.samples[v_axis];
+
+            let grid = |u: usize, v: usize| {
+                let mut g = [0; 3];
+                g[axis] = w;
+                g[u_axis] = u;
+                g[v_axis] = v;
+                g
+            };
+
+            // Cache the distance field values across the face
+            let mut plane = vec![0f32; size_u * size_v];
+            for v in 0..size_v {
+                for u in 0..size_u {
+                    let p = self.position(grid(u, v));
+                    plane[v * size_u + u] = source.sample(p.x, p.y, p.z);
+                }
+            }
+
+            let mut cell = TransitionCell {
+                face,
+                samples: [[0; 3]; 13],
+                values: [0.0; 13],
+            };
+
+            for v in (0..size_v - 1).step_by(2) {
+                for u in (0..size_u - 1).step_by(2) {
+                    let mut case = 0;
+                    for (i, offset) in TRANSITION_SAMPLES.iter().enumerate() {
+                        let su = u + offset[0];
+                        let sv = v + offset[1];
+                        cell.samples[i] = grid(su, sv);
+                        cell.values[i] = plane[sv * size_u + su];
+                        if i < 9 && cell.values[i] <= self.iso_level {
+                            case |= 1 << i;
+                        }
+                    }
+
+                    let triangles = &TRANSITION_TRIANGLES[case];
+                    let mut i = 0;
+                    while i < triangles.len() && triangles[i] >= 0 {
+                        let mut triangle = [0u32; 3];
+                        for j in 0..3 {
+                            let edge = triangles[i + j] as usize;
+                            triangle[j] = self.edge_index(&cell, edge, index, normal, sink);
+                        }
+
+                        // Transition triangles are wound for a negative face, so mirror them
+                        // on positive faces.
+                        if positive {
+                            sink.add_triangle(triangle[0], triangle[2], triangle[1]);
+                        } else {
+                            sink.add_triangle(triangle[0], triangle[1], triangle[2]);
+                        }
+
+                        i += 3;
+                    }
+                }
+            }
+        }
+    }
+
+    fn edge_index<N, M>(
+        &mut self,
+        cell: &TransitionCell,
+        edge: usize,
+        index: &mut u32,
+        normal: &N,
+        sink: &mut M,
+    ) -> u32
+    where
+        N: Fn(Vec3) -> Option<Vec3>,
+        M: MeshSink,
+    {
+        let u = TRANSITION_EDGES[edge][0];
+        let v = TRANSITION_EDGES[edge][1];
+
+        let low_resolution = u >= 9;
+        let key = seam_edge(
+            if low_resolution {
+                cell.face
+            } else {
+                HIGH_RESOLUTION
+            },
+            cell.samples[u],
+            cell.samples[v],
+        );
+
+        if let Some(&existing) = self.seam.get(&key) {
+            return existing;
+        }
+
+        let vertex = edge_vertex(
+            self.position(cell.samples[u]),
+            self.position(cell.samples[v]),
+            cell.values[u],
+            cell.values[v],
+            self.iso_level,
+        );
+        // Vertices on the low-resolution face are shared with the neighbouring chunk, so they
+        // never move.
+        let vertex = if low_resolution {
+            vertex
+        } else {
+            self.shrink(vertex)
+        };
+
+        let result = *index;
+        *index += 1;
+        self.seam.insert(key, result);
+        sink.add_vertex(vertex, normal(vertex));
+        result
+    }
+
+    // Must match the way the regular cells compute sample positions, so that shared vertices are
+    // bit-identical.
+    fn position(&self, g: [usize; 3]) -> Vec3 {
+        let origin = self.region.origin;
+        Vec3::new(
+            origin.x + g[0] as f32 * self.step.x,
+            origin.y + g[1] as f32 * self.step.y,
+            origin.z + g[2] as f32 * self.step.z,
+        )
+    }
+}
+
+fn seam_edge(face: usize, a: [usize; 3], b: [usize; 3]) -> SeamEdge {
+    if b < a {
+        (face, b, a)
+    } else {
+        (face, a, b)
+    }
+}
@@ -0,0 +1,2222 @@
+// Copyright 2018 Tristam MacDonald
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// The offsets of each sample in a transition cell, in units of high-resolution cells along the
+/// two axes of the face. Samples 0 through 8 lie on the high-resolution face, and samples 9
+/// through 12 on the low-resolution face, where they share the values of samples 0, 2, 6 and 8.
+pub const TRANSITION_SAMPLES: [[usize; 2]; 13] = [
+    [0, 0],
+    [1, 0],
+    [2, 0],
+    [0, 1],
+    [1, 1],
+    [2, 1],
+    [0, 2],
+    [1, 2],
+    [2, 2],
+    [0, 0],
+    [2, 0],
+    [0, 2],
+    [2, 2],
+];
+
+/// The samples used by each edge in a transition cell. Edges 12 through 15 lie on the
+/// low-resolution face.
+pub const TRANSITION_EDGES: [[usize; 2]; 16] = [
+    [0, 1],
+    [1, 2],
+    [3, 4],
+    [4, 5],
+    [6, 7],
+    [7, 8],
+    [0, 3],
+    [3, 6],
+    [1, 4],
+    [4, 7],
+    [2, 5],
+    [5, 8],
+    [9, 10],
+    [11, 12],
+    [9, 11],
+    [10, 12],
+];
+
+/// Maps the signs of the 9 samples on the high-resolution face of a transition cell to the set
+/// of triangles spanning the active edges. Unused values are set to -1.
+///
+/// This is not Lengyel's transition table. It was generated by linking the contours on each face
+/// of the cell into loops, so that every case agrees with the regular cells beside it, and fanning
+/// each loop into triangles. The test at the end of this module generates it again, and checks
+/// that it matches.
+pub const TRANSITION_TRIANGLES: [[i8; 28]; 512] = [
+    [
+        -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1, -1,
+    ],
+    [
+        0, 6, 14, 0, 14, 12, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1, -1,
+    ],
+    [
+        0, 1, 8, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1, -1,
+    ],
+    [
+        1, 8, 6, 1, 6, 14, 1, 14, 12, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        1, 12, 15, 1, 15, 10, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1, -1,
+    ],
+    [
+        0, 6, 14, 0, 14, 15, 0, 15, 10, 0, 10, 1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 12, 15, 0, 15, 10, 0, 10, 8, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        6, 14, 15, 6, 15, 10, 6, 10, 8, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        2, 7, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1, -1,
+    ],
+    [
+        0, 2, 7, 0, 7, 14, 0, 14, 12, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 1, 8, 2, 7, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        1, 8, 2, 1, 2, 7, 1, 7, 14, 1, 14, 12, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        1, 12, 15, 1, 15, 10, 2, 7, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 2, 7, 0, 7, 14, 0, 14, 15, 0, 15, 10, 0, 10, 1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 12, 15, 0, 15, 10, 0, 10, 8, 2, 7, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        2, 7, 14, 2, 14, 15, 2, 15, 10, 2, 10, 8, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        2, 8, 3, 2, 3, 9, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 6, 14, 0, 14, 12, 2, 8, 3, 2, 3, 9, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 1, 3, 0, 3, 9, 0, 9, 2, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        1, 3, 9, 1, 9, 2, 1, 2, 6, 1, 6, 14, 1, 14, 12, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 12, 15, 1, 15, 10, 2, 8, 3, 2, 3, 9, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 6, 14, 0, 14, 15, 0, 15, 10, 0, 10, 1, 2, 8, 3, 2, 3, 9, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 12, 15, 0, 15, 10, 0, 10, 3, 0, 3, 9, 0, 9, 2, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        2, 6, 14, 2, 14, 15, 2, 15, 10, 2, 10, 3, 2, 3, 9, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        3, 9, 7, 3, 7, 6, 3, 6, 8, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 8, 3, 0, 3, 9, 0, 9, 7, 0, 7, 14, 0, 14, 12, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 1, 3, 0, 3, 9, 0, 9, 7, 0, 7, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 3, 9, 1, 9, 7, 1, 7, 14, 1, 14, 12, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        1, 12, 15, 1, 15, 10, 3, 9, 7, 3, 7, 6, 3, 6, 8, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 8, 3, 0, 3, 9, 0, 9, 7, 0, 7, 14, 0, 14, 15, 0, 15, 10, 0, 10, 1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 12, 15, 0, 15, 10, 0, 10, 3, 0, 3, 9, 0, 9, 7, 0, 7, 6, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        3, 9, 7, 3, 7, 14, 3, 14, 15, 3, 15, 10, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        3, 10, 11, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1, -1,
+    ],
+    [
+        0, 6, 14, 0, 14, 12, 3, 10, 11, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 1, 8, 3, 10, 11, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        1, 8, 6, 1, 6, 14, 1, 14, 12, 3, 10, 11, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        1, 12, 15, 1, 15, 11, 1, 11, 3, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 6, 14, 0, 14, 15, 0, 15, 11, 0, 11, 3, 0, 3, 1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 12, 15, 0, 15, 11, 0, 11, 3, 0, 3, 8, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        3, 8, 6, 3, 6, 14, 3, 14, 15, 3, 15, 11, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        2, 7, 6, 3, 10, 11, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 2, 7, 0, 7, 14, 0, 14, 12, 3, 10, 11, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 1, 8, 2, 7, 6, 3, 10, 11, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        1, 8, 2, 1, 2, 7, 1, 7, 14, 1, 14, 12, 3, 10, 11, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        1, 12, 15, 1, 15, 11, 1, 11, 3, 2, 7, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 2, 7, 0, 7, 14, 0, 14, 15, 0, 15, 11, 0, 11, 3, 0, 3, 1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 12, 15, 0, 15, 11, 0, 11, 3, 0, 3, 8, 2, 7, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        2, 7, 14, 2, 14, 15, 2, 15, 11, 2, 11, 3, 2, 3, 8, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        2, 8, 10, 2, 10, 11, 2, 11, 9, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 6, 14, 0, 14, 12, 2, 8, 10, 2, 10, 11, 2, 11, 9, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 1, 10, 0, 10, 11, 0, 11, 9, 0, 9, 2, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        1, 10, 11, 1, 11, 9, 1, 9, 2, 1, 2, 6, 1, 6, 14, 1, 14, 12, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 12, 15, 1, 15, 11, 1, 11, 9, 1, 9, 2, 1, 2, 8, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 6, 14, 0, 14, 15, 0, 15, 11, 0, 11, 9, 0, 9, 2, 0, 2, 8, 0, 8, 1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 12, 15, 0, 15, 11, 0, 11, 9, 0, 9, 2, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        2, 6, 14, 2, 14, 15, 2, 15, 11, 2, 11, 9, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        6, 8, 10, 6, 10, 11, 6, 11, 9, 6, 9, 7, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 8, 10, 0, 10, 11, 0, 11, 9, 0, 9, 7, 0, 7, 14, 0, 14, 12, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 1, 10, 0, 10, 11, 0, 11, 9, 0, 9, 7, 0, 7, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        1, 10, 11, 1, 11, 9, 1, 9, 7, 1, 7, 14, 1, 14, 12, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        1, 12, 15, 1, 15, 11, 1, 11, 9, 1, 9, 7, 1, 7, 6, 1, 6, 8, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 8, 1, 7, 14, 15, 7, 15, 11, 7, 11, 9, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 12, 15, 0, 15, 11, 0, 11, 9, 0, 9, 7, 0, 7, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        7, 14, 15, 7, 15, 11, 7, 11, 9, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        4, 13, 14, 4, 14, 7, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1, -1,
+    ],
+    [
+        0, 6, 7, 0, 7, 4, 0, 4, 13, 0, 13, 12, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 1, 8, 4, 13, 14, 4, 14, 7, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        1, 8, 6, 1, 6, 7, 1, 7, 4, 1, 4, 13, 1, 13, 12, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 12, 15, 1, 15, 10, 4, 13, 14, 4, 14, 7, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 6, 7, 0, 7, 4, 0, 4, 13, 0, 13, 15, 0, 15, 10, 0, 10, 1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 12, 15, 0, 15, 10, 0, 10, 8, 4, 13, 14, 4, 14, 7, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        4, 13, 15, 4, 15, 10, 4, 10, 8, 4, 8, 6, 4, 6, 7, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        2, 4, 13, 2, 13, 14, 2, 14, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 2, 4, 0, 4, 13, 0, 13, 12, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 1, 8, 2, 4, 13, 2, 13, 14, 2, 14, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        1, 8, 2, 1, 2, 4, 1, 4, 13, 1, 13, 12, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        1, 12, 15, 1, 15, 10, 2, 4, 13, 2, 13, 14, 2, 14, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 2, 4, 0, 4, 13, 0, 13, 15, 0, 15, 10, 0, 10, 1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 12, 15, 0, 15, 10, 0, 10, 8, 2, 4, 13, 2, 13, 14, 2, 14, 6, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        2, 4, 13, 2, 13, 15, 2, 15, 10, 2, 10, 8, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        2, 8, 3, 2, 3, 9, 4, 13, 14, 4, 14, 7, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 6, 7, 0, 7, 4, 0, 4, 13, 0, 13, 12, 2, 8, 3, 2, 3, 9, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 1, 3, 0, 3, 9, 0, 9, 2, 4, 13, 14, 4, 14, 7, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 3, 9, 1, 9, 2, 1, 2, 6, 1, 6, 7, 1, 7, 4, 1, 4, 13, 1, 13, 12, -1, -1, -1, -1, -1, -1,
+        -1,
+    ],
+    [
+        1, 12, 15, 1, 15, 10, 2, 8, 3, 2, 3, 9, 4, 13, 14, 4, 14, 7, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 6, 7, 0, 7, 4, 0, 4, 13, 0, 13, 15, 0, 15, 10, 0, 10, 1, 2, 8, 3, 2, 3, 9, -1, -1, -1,
+        -1,
+    ],
+    [
+        0, 12, 15, 0, 15, 10, 0, 10, 3, 0, 3, 9, 0, 9, 2, 4, 13, 14, 4, 14, 7, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        2, 6, 7, 2, 7, 4, 2, 4, 13, 2, 13, 15, 2, 15, 10, 2, 10, 3, 2, 3, 9, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        3, 9, 4, 3, 4, 13, 3, 13, 14, 3, 14, 6, 3, 6, 8, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 8, 3, 0, 3, 9, 0, 9, 4, 0, 4, 13, 0, 13, 12, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 1, 3, 0, 3, 9, 0, 9, 4, 0, 4, 13, 0, 13, 14, 0, 14, 6, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 3, 9, 1, 9, 4, 1, 4, 13, 1, 13, 12, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        1, 12, 15, 1, 15, 10, 3, 9, 4, 3, 4, 13, 3, 13, 14, 3, 14, 6, 3, 6, 8, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 8, 3, 0, 3, 9, 0, 9, 4, 0, 4, 13, 0, 13, 15, 0, 15, 10, 0, 10, 1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 12, 15, 0, 15, 10, 0, 10, 3, 0, 3, 9, 0, 9, 4, 0, 4, 13, 0, 13, 14, 0, 14, 6, -1, -1,
+        -1, -1,
+    ],
+    [
+        3, 9, 4, 3, 4, 13, 3, 13, 15, 3, 15, 10, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        3, 10, 11, 4, 13, 14, 4, 14, 7, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 6, 7, 0, 7, 4, 0, 4, 13, 0, 13, 12, 3, 10, 11, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 1, 8, 3, 10, 11, 4, 13, 14, 4, 14, 7, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        1, 8, 6, 1, 6, 7, 1, 7, 4, 1, 4, 13, 1, 13, 12, 3, 10, 11, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 12, 15, 1, 15, 11, 1, 11, 3, 4, 13, 14, 4, 14, 7, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 6, 7, 0, 7, 4, 0, 4, 13, 0, 13, 15, 0, 15, 11, 0, 11, 3, 0, 3, 1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 12, 15, 0, 15, 11, 0, 11, 3, 0, 3, 8, 4, 13, 14, 4, 14, 7, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        3, 8, 6, 3, 6, 7, 3, 7, 4, 3, 4, 13, 3, 13, 15, 3, 15, 11, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        2, 4, 13, 2, 13, 14, 2, 14, 6, 3, 10, 11, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 2, 4, 0, 4, 13, 0, 13, 12, 3, 10, 11, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 1, 8, 2, 4, 13, 2, 13, 14, 2, 14, 6, 3, 10, 11, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        1, 8, 2, 1, 2, 4, 1, 4, 13, 1, 13, 12, 3, 10, 11, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        1, 12, 15, 1, 15, 11, 1, 11, 3, 2, 4, 13, 2, 13, 14, 2, 14, 6, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 2, 4, 0, 4, 13, 0, 13, 15, 0, 15, 11, 0, 11, 3, 0, 3, 1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 12, 15, 0, 15, 11, 0, 11, 3, 0, 3, 8, 2, 4, 13, 2, 13, 14, 2, 14, 6, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        2, 4, 13, 2, 13, 15, 2, 15, 11, 2, 11, 3, 2, 3, 8, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        2, 8, 10, 2, 10, 11, 2, 11, 9, 4, 13, 14, 4, 14, 7, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 6, 7, 0, 7, 4, 0, 4, 13, 0, 13, 12, 2, 8, 10, 2, 10, 11, 2, 11, 9, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 1, 10, 0, 10, 11, 0, 11, 9, 0, 9, 2, 4, 13, 14, 4, 14, 7, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        1, 10, 11, 1, 11, 9, 1, 9, 2, 1, 2, 6, 1, 6, 7, 1, 7, 4, 1, 4, 13, 1, 13, 12, -1, -1, -1,
+        -1,
+    ],
+    [
+        1, 12, 15, 1, 15, 11, 1, 11, 9, 1, 9, 2, 1, 2, 8, 4, 13, 14, 4, 14, 7, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 6, 7, 0, 7, 4, 0, 4, 13, 0, 13, 15, 0, 15, 11, 0, 11, 9, 0, 9, 2, 0, 2, 8, 0, 8, 1, -1,
+    ],
+    [
+        0, 12, 15, 0, 15, 11, 0, 11, 9, 0, 9, 2, 4, 13, 14, 4, 14, 7, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        2, 6, 7, 2, 7, 4, 2, 4, 13, 2, 13, 15, 2, 15, 11, 2, 11, 9, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        4, 13, 14, 4, 14, 6, 4, 6, 8, 4, 8, 10, 4, 10, 11, 4, 11, 9, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 8, 10, 0, 10, 11, 0, 11, 9, 0, 9, 4, 0, 4, 13, 0, 13, 12, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 1, 10, 0, 10, 11, 0, 11, 9, 0, 9, 4, 0, 4, 13, 0, 13, 14, 0, 14, 6, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 10, 11, 1, 11, 9, 1, 9, 4, 1, 4, 13, 1, 13, 12, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        1, 12, 15, 1, 15, 11, 1, 11, 9, 1, 9, 4, 1, 4, 13, 1, 13, 14, 1, 14, 6, 1, 6, 8, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 8, 1, 4, 13, 15, 4, 15, 11, 4, 11, 9, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 12, 15, 0, 15, 11, 0, 11, 9, 0, 9, 4, 0, 4, 13, 0, 13, 14, 0, 14, 6, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        4, 13, 15, 4, 15, 11, 4, 11, 9, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        4, 9, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1, -1,
+    ],
+    [
+        0, 6, 14, 0, 14, 12, 4, 9, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 1, 8, 4, 9, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        1, 8, 6, 1, 6, 14, 1, 14, 12, 4, 9, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        1, 12, 15, 1, 15, 10, 4, 9, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 6, 14, 0, 14, 15, 0, 15, 10, 0, 10, 1, 4, 9, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 12, 15, 0, 15, 10, 0, 10, 8, 4, 9, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        4, 9, 5, 6, 14, 15, 6, 15, 10, 6, 10, 8, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        2, 7, 6, 4, 9, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 2, 7, 0, 7, 14, 0, 14, 12, 4, 9, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 1, 8, 2, 7, 6, 4, 9, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        1, 8, 2, 1, 2, 7, 1, 7, 14, 1, 14, 12, 4, 9, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 12, 15, 1, 15, 10, 2, 7, 6, 4, 9, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 2, 7, 0, 7, 14, 0, 14, 15, 0, 15, 10, 0, 10, 1, 4, 9, 5, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 12, 15, 0, 15, 10, 0, 10, 8, 2, 7, 6, 4, 9, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        2, 7, 14, 2, 14, 15, 2, 15, 10, 2, 10, 8, 4, 9, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        2, 8, 3, 2, 3, 5, 2, 5, 4, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 6, 14, 0, 14, 12, 2, 8, 3, 2, 3, 5, 2, 5, 4, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 1, 3, 0, 3, 5, 0, 5, 4, 0, 4, 2, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 3, 5, 1, 5, 4, 1, 4, 2, 1, 2, 6, 1, 6, 14, 1, 14, 12, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 12, 15, 1, 15, 10, 2, 8, 3, 2, 3, 5, 2, 5, 4, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 6, 14, 0, 14, 15, 0, 15, 10, 0, 10, 1, 2, 8, 3, 2, 3, 5, 2, 5, 4, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 12, 15, 0, 15, 10, 0, 10, 3, 0, 3, 5, 0, 5, 4, 0, 4, 2, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        2, 6, 14, 2, 14, 15, 2, 15, 10, 2, 10, 3, 2, 3, 5, 2, 5, 4, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        3, 5, 4, 3, 4, 7, 3, 7, 6, 3, 6, 8, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 8, 3, 0, 3, 5, 0, 5, 4, 0, 4, 7, 0, 7, 14, 0, 14, 12, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 1, 3, 0, 3, 5, 0, 5, 4, 0, 4, 7, 0, 7, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 3, 5, 1, 5, 4, 1, 4, 7, 1, 7, 14, 1, 14, 12, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 12, 15, 1, 15, 10, 3, 5, 4, 3, 4, 7, 3, 7, 6, 3, 6, 8, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 8, 3, 0, 3, 5, 0, 5, 4, 0, 4, 7, 0, 7, 14, 0, 14, 15, 0, 15, 10, 0, 10, 1, -1, -1, -1,
+        -1,
+    ],
+    [
+        0, 12, 15, 0, 15, 10, 0, 10, 3, 0, 3, 5, 0, 5, 4, 0, 4, 7, 0, 7, 6, -1, -1, -1, -1, -1, -1,
+        -1,
+    ],
+    [
+        3, 5, 4, 3, 4, 7, 3, 7, 14, 3, 14, 15, 3, 15, 10, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        3, 10, 11, 4, 9, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 6, 14, 0, 14, 12, 3, 10, 11, 4, 9, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 1, 8, 3, 10, 11, 4, 9, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        1, 8, 6, 1, 6, 14, 1, 14, 12, 3, 10, 11, 4, 9, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        1, 12, 15, 1, 15, 11, 1, 11, 3, 4, 9, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 6, 14, 0, 14, 15, 0, 15, 11, 0, 11, 3, 0, 3, 1, 4, 9, 5, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 12, 15, 0, 15, 11, 0, 11, 3, 0, 3, 8, 4, 9, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        3, 8, 6, 3, 6, 14, 3, 14, 15, 3, 15, 11, 4, 9, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        2, 7, 6, 3, 10, 11, 4, 9, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 2, 7, 0, 7, 14, 0, 14, 12, 3, 10, 11, 4, 9, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 1, 8, 2, 7, 6, 3, 10, 11, 4, 9, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        1, 8, 2, 1, 2, 7, 1, 7, 14, 1, 14, 12, 3, 10, 11, 4, 9, 5, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 12, 15, 1, 15, 11, 1, 11, 3, 2, 7, 6, 4, 9, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 2, 7, 0, 7, 14, 0, 14, 15, 0, 15, 11, 0, 11, 3, 0, 3, 1, 4, 9, 5, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 12, 15, 0, 15, 11, 0, 11, 3, 0, 3, 8, 2, 7, 6, 4, 9, 5, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        2, 7, 14, 2, 14, 15, 2, 15, 11, 2, 11, 3, 2, 3, 8, 4, 9, 5, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        2, 8, 10, 2, 10, 11, 2, 11, 5, 2, 5, 4, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 6, 14, 0, 14, 12, 2, 8, 10, 2, 10, 11, 2, 11, 5, 2, 5, 4, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 1, 10, 0, 10, 11, 0, 11, 5, 0, 5, 4, 0, 4, 2, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        1, 10, 11, 1, 11, 5, 1, 5, 4, 1, 4, 2, 1, 2, 6, 1, 6, 14, 1, 14, 12, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 12, 15, 1, 15, 11, 1, 11, 5, 1, 5, 4, 1, 4, 2, 1, 2, 8, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 6, 14, 0, 14, 15, 0, 15, 11, 0, 11, 5, 0, 5, 4, 0, 4, 2, 0, 2, 8, 0, 8, 1, -1, -1, -1,
+        -1,
+    ],
+    [
+        0, 12, 15, 0, 15, 11, 0, 11, 5, 0, 5, 4, 0, 4, 2, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        2, 6, 14, 2, 14, 15, 2, 15, 11, 2, 11, 5, 2, 5, 4, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        4, 7, 6, 4, 6, 8, 4, 8, 10, 4, 10, 11, 4, 11, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 8, 10, 0, 10, 11, 0, 11, 5, 0, 5, 4, 0, 4, 7, 0, 7, 14, 0, 14, 12, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 1, 10, 0, 10, 11, 0, 11, 5, 0, 5, 4, 0, 4, 7, 0, 7, 6, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 10, 11, 1, 11, 5, 1, 5, 4, 1, 4, 7, 1, 7, 14, 1, 14, 12, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 12, 15, 1, 15, 11, 1, 11, 5, 1, 5, 4, 1, 4, 7, 1, 7, 6, 1, 6, 8, -1, -1, -1, -1, -1, -1,
+        -1,
+    ],
+    [
+        0, 8, 1, 4, 7, 14, 4, 14, 15, 4, 15, 11, 4, 11, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 12, 15, 0, 15, 11, 0, 11, 5, 0, 5, 4, 0, 4, 7, 0, 7, 6, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        4, 7, 14, 4, 14, 15, 4, 15, 11, 4, 11, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        5, 13, 14, 5, 14, 7, 5, 7, 9, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 6, 7, 0, 7, 9, 0, 9, 5, 0, 5, 13, 0, 13, 12, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 1, 8, 5, 13, 14, 5, 14, 7, 5, 7, 9, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        1, 8, 6, 1, 6, 7, 1, 7, 9, 1, 9, 5, 1, 5, 13, 1, 13, 12, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 12, 15, 1, 15, 10, 5, 13, 14, 5, 14, 7, 5, 7, 9, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 6, 7, 0, 7, 9, 0, 9, 5, 0, 5, 13, 0, 13, 15, 0, 15, 10, 0, 10, 1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 12, 15, 0, 15, 10, 0, 10, 8, 5, 13, 14, 5, 14, 7, 5, 7, 9, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        5, 13, 15, 5, 15, 10, 5, 10, 8, 5, 8, 6, 5, 6, 7, 5, 7, 9, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        2, 9, 5, 2, 5, 13, 2, 13, 14, 2, 14, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 2, 9, 0, 9, 5, 0, 5, 13, 0, 13, 12, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 1, 8, 2, 9, 5, 2, 5, 13, 2, 13, 14, 2, 14, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        1, 8, 2, 1, 2, 9, 1, 9, 5, 1, 5, 13, 1, 13, 12, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 12, 15, 1, 15, 10, 2, 9, 5, 2, 5, 13, 2, 13, 14, 2, 14, 6, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 2, 9, 0, 9, 5, 0, 5, 13, 0, 13, 15, 0, 15, 10, 0, 10, 1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 12, 15, 0, 15, 10, 0, 10, 8, 2, 9, 5, 2, 5, 13, 2, 13, 14, 2, 14, 6, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        2, 9, 5, 2, 5, 13, 2, 13, 15, 2, 15, 10, 2, 10, 8, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        2, 8, 3, 2, 3, 5, 2, 5, 13, 2, 13, 14, 2, 14, 7, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 6, 7, 0, 7, 2, 0, 2, 8, 0, 8, 3, 0, 3, 5, 0, 5, 13, 0, 13, 12, -1, -1, -1, -1, -1, -1,
+        -1,
+    ],
+    [
+        0, 1, 3, 0, 3, 5, 0, 5, 13, 0, 13, 14, 0, 14, 7, 0, 7, 2, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 3, 5, 1, 5, 13, 1, 13, 12, 2, 6, 7, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        1, 12, 15, 1, 15, 10, 2, 8, 3, 2, 3, 5, 2, 5, 13, 2, 13, 14, 2, 14, 7, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 6, 7, 0, 7, 2, 0, 2, 8, 0, 8, 3, 0, 3, 5, 0, 5, 13, 0, 13, 15, 0, 15, 10, 0, 10, 1, -1,
+    ],
+    [
+        0, 12, 15, 0, 15, 10, 0, 10, 3, 0, 3, 5, 0, 5, 13, 0, 13, 14, 0, 14, 7, 0, 7, 2, -1, -1,
+        -1, -1,
+    ],
+    [
+        2, 6, 7, 3, 5, 13, 3, 13, 15, 3, 15, 10, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        3, 5, 13, 3, 13, 14, 3, 14, 6, 3, 6, 8, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 8, 3, 0, 3, 5, 0, 5, 13, 0, 13, 12, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 1, 3, 0, 3, 5, 0, 5, 13, 0, 13, 14, 0, 14, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        1, 3, 5, 1, 5, 13, 1, 13, 12, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        1, 12, 15, 1, 15, 10, 3, 5, 13, 3, 13, 14, 3, 14, 6, 3, 6, 8, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 8, 3, 0, 3, 5, 0, 5, 13, 0, 13, 15, 0, 15, 10, 0, 10, 1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 12, 15, 0, 15, 10, 0, 10, 3, 0, 3, 5, 0, 5, 13, 0, 13, 14, 0, 14, 6, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        3, 5, 13, 3, 13, 15, 3, 15, 10, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        3, 10, 11, 5, 13, 14, 5, 14, 7, 5, 7, 9, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 6, 7, 0, 7, 9, 0, 9, 5, 0, 5, 13, 0, 13, 12, 3, 10, 11, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 1, 8, 3, 10, 11, 5, 13, 14, 5, 14, 7, 5, 7, 9, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        1, 8, 6, 1, 6, 7, 1, 7, 9, 1, 9, 5, 1, 5, 13, 1, 13, 12, 3, 10, 11, -1, -1, -1, -1, -1, -1,
+        -1,
+    ],
+    [
+        1, 12, 15, 1, 15, 11, 1, 11, 3, 5, 13, 14, 5, 14, 7, 5, 7, 9, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 6, 7, 0, 7, 9, 0, 9, 5, 0, 5, 13, 0, 13, 15, 0, 15, 11, 0, 11, 3, 0, 3, 1, -1, -1, -1,
+        -1,
+    ],
+    [
+        0, 12, 15, 0, 15, 11, 0, 11, 3, 0, 3, 8, 5, 13, 14, 5, 14, 7, 5, 7, 9, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        3, 8, 6, 3, 6, 7, 3, 7, 9, 3, 9, 5, 3, 5, 13, 3, 13, 15, 3, 15, 11, -1, -1, -1, -1, -1, -1,
+        -1,
+    ],
+    [
+        2, 9, 5, 2, 5, 13, 2, 13, 14, 2, 14, 6, 3, 10, 11, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 2, 9, 0, 9, 5, 0, 5, 13, 0, 13, 12, 3, 10, 11, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 1, 8, 2, 9, 5, 2, 5, 13, 2, 13, 14, 2, 14, 6, 3, 10, 11, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 8, 2, 1, 2, 9, 1, 9, 5, 1, 5, 13, 1, 13, 12, 3, 10, 11, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 12, 15, 1, 15, 11, 1, 11, 3, 2, 9, 5, 2, 5, 13, 2, 13, 14, 2, 14, 6, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 2, 9, 0, 9, 5, 0, 5, 13, 0, 13, 15, 0, 15, 11, 0, 11, 3, 0, 3, 1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 12, 15, 0, 15, 11, 0, 11, 3, 0, 3, 8, 2, 9, 5, 2, 5, 13, 2, 13, 14, 2, 14, 6, -1, -1,
+        -1, -1,
+    ],
+    [
+        2, 9, 5, 2, 5, 13, 2, 13, 15, 2, 15, 11, 2, 11, 3, 2, 3, 8, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        2, 8, 10, 2, 10, 11, 2, 11, 5, 2, 5, 13, 2, 13, 14, 2, 14, 7, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 6, 7, 0, 7, 2, 0, 2, 8, 0, 8, 10, 0, 10, 11, 0, 11, 5, 0, 5, 13, 0, 13, 12, -1, -1, -1,
+        -1,
+    ],
+    [
+        0, 1, 10, 0, 10, 11, 0, 11, 5, 0, 5, 13, 0, 13, 14, 0, 14, 7, 0, 7, 2, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 10, 11, 1, 11, 5, 1, 5, 13, 1, 13, 12, 2, 6, 7, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        1, 12, 15, 1, 15, 11, 1, 11, 5, 1, 5, 13, 1, 13, 14, 1, 14, 7, 1, 7, 2, 1, 2, 8, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 6, 7, 0, 7, 2, 0, 2, 8, 0, 8, 1, 5, 13, 15, 5, 15, 11, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 12, 15, 0, 15, 11, 0, 11, 5, 0, 5, 13, 0, 13, 14, 0, 14, 7, 0, 7, 2, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        2, 6, 7, 5, 13, 15, 5, 15, 11, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        5, 13, 14, 5, 14, 6, 5, 6, 8, 5, 8, 10, 5, 10, 11, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 8, 10, 0, 10, 11, 0, 11, 5, 0, 5, 13, 0, 13, 12, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 1, 10, 0, 10, 11, 0, 11, 5, 0, 5, 13, 0, 13, 14, 0, 14, 6, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        1, 10, 11, 1, 11, 5, 1, 5, 13, 1, 13, 12, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        1, 12, 15, 1, 15, 11, 1, 11, 5, 1, 5, 13, 1, 13, 14, 1, 14, 6, 1, 6, 8, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 8, 1, 5, 13, 15, 5, 15, 11, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 12, 15, 0, 15, 11, 0, 11, 5, 0, 5, 13, 0, 13, 14, 0, 14, 6, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        5, 13, 15, 5, 15, 11, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1, -1,
+    ],
+    [
+        5, 11, 15, 5, 15, 13, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1, -1,
+    ],
+    [
+        0, 6, 14, 0, 14, 12, 5, 11, 15, 5, 15, 13, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 1, 8, 5, 11, 15, 5, 15, 13, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        1, 8, 6, 1, 6, 14, 1, 14, 12, 5, 11, 15, 5, 15, 13, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        1, 12, 13, 1, 13, 5, 1, 5, 11, 1, 11, 10, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 6, 14, 0, 14, 13, 0, 13, 5, 0, 5, 11, 0, 11, 10, 0, 10, 1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 12, 13, 0, 13, 5, 0, 5, 11, 0, 11, 10, 0, 10, 8, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        5, 11, 10, 5, 10, 8, 5, 8, 6, 5, 6, 14, 5, 14, 13, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        2, 7, 6, 5, 11, 15, 5, 15, 13, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 2, 7, 0, 7, 14, 0, 14, 12, 5, 11, 15, 5, 15, 13, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 1, 8, 2, 7, 6, 5, 11, 15, 5, 15, 13, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        1, 8, 2, 1, 2, 7, 1, 7, 14, 1, 14, 12, 5, 11, 15, 5, 15, 13, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        1, 12, 13, 1, 13, 5, 1, 5, 11, 1, 11, 10, 2, 7, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 2, 7, 0, 7, 14, 0, 14, 13, 0, 13, 5, 0, 5, 11, 0, 11, 10, 0, 10, 1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 12, 13, 0, 13, 5, 0, 5, 11, 0, 11, 10, 0, 10, 8, 2, 7, 6, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        2, 7, 14, 2, 14, 13, 2, 13, 5, 2, 5, 11, 2, 11, 10, 2, 10, 8, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        2, 8, 3, 2, 3, 9, 5, 11, 15, 5, 15, 13, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 6, 14, 0, 14, 12, 2, 8, 3, 2, 3, 9, 5, 11, 15, 5, 15, 13, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 1, 3, 0, 3, 9, 0, 9, 2, 5, 11, 15, 5, 15, 13, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        1, 3, 9, 1, 9, 2, 1, 2, 6, 1, 6, 14, 1, 14, 12, 5, 11, 15, 5, 15, 13, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 12, 13, 1, 13, 5, 1, 5, 11, 1, 11, 10, 2, 8, 3, 2, 3, 9, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 6, 14, 0, 14, 13, 0, 13, 5, 0, 5, 11, 0, 11, 10, 0, 10, 1, 2, 8, 3, 2, 3, 9, -1, -1, -1,
+        -1,
+    ],
+    [
+        0, 12, 13, 0, 13, 5, 0, 5, 11, 0, 11, 10, 0, 10, 3, 0, 3, 9, 0, 9, 2, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        2, 6, 14, 2, 14, 13, 2, 13, 5, 2, 5, 11, 2, 11, 10, 2, 10, 3, 2, 3, 9, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        3, 9, 7, 3, 7, 6, 3, 6, 8, 5, 11, 15, 5, 15, 13, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 8, 3, 0, 3, 9, 0, 9, 7, 0, 7, 14, 0, 14, 12, 5, 11, 15, 5, 15, 13, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 1, 3, 0, 3, 9, 0, 9, 7, 0, 7, 6, 5, 11, 15, 5, 15, 13, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 3, 9, 1, 9, 7, 1, 7, 14, 1, 14, 12, 5, 11, 15, 5, 15, 13, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        1, 12, 13, 1, 13, 5, 1, 5, 11, 1, 11, 10, 3, 9, 7, 3, 7, 6, 3, 6, 8, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 8, 3, 0, 3, 9, 0, 9, 7, 0, 7, 14, 0, 14, 13, 0, 13, 5, 0, 5, 11, 0, 11, 10, 0, 10, 1, -1,
+    ],
+    [
+        0, 12, 13, 0, 13, 5, 0, 5, 11, 0, 11, 10, 0, 10, 3, 0, 3, 9, 0, 9, 7, 0, 7, 6, -1, -1, -1,
+        -1,
+    ],
+    [
+        3, 9, 7, 3, 7, 14, 3, 14, 13, 3, 13, 5, 3, 5, 11, 3, 11, 10, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        3, 10, 15, 3, 15, 13, 3, 13, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 6, 14, 0, 14, 12, 3, 10, 15, 3, 15, 13, 3, 13, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 1, 8, 3, 10, 15, 3, 15, 13, 3, 13, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        1, 8, 6, 1, 6, 14, 1, 14, 12, 3, 10, 15, 3, 15, 13, 3, 13, 5, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        1, 12, 13, 1, 13, 5, 1, 5, 3, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 6, 14, 0, 14, 13, 0, 13, 5, 0, 5, 3, 0, 3, 1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 12, 13, 0, 13, 5, 0, 5, 3, 0, 3, 8, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        3, 8, 6, 3, 6, 14, 3, 14, 13, 3, 13, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        2, 7, 6, 3, 10, 15, 3, 15, 13, 3, 13, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 2, 7, 0, 7, 14, 0, 14, 12, 3, 10, 15, 3, 15, 13, 3, 13, 5, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 1, 8, 2, 7, 6, 3, 10, 15, 3, 15, 13, 3, 13, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        1, 8, 2, 1, 2, 7, 1, 7, 14, 1, 14, 12, 3, 10, 15, 3, 15, 13, 3, 13, 5, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 12, 13, 1, 13, 5, 1, 5, 3, 2, 7, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 2, 7, 0, 7, 14, 0, 14, 13, 0, 13, 5, 0, 5, 3, 0, 3, 1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 12, 13, 0, 13, 5, 0, 5, 3, 0, 3, 8, 2, 7, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        2, 7, 14, 2, 14, 13, 2, 13, 5, 2, 5, 3, 2, 3, 8, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        2, 8, 10, 2, 10, 15, 2, 15, 13, 2, 13, 5, 2, 5, 9, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 6, 14, 0, 14, 12, 2, 8, 10, 2, 10, 15, 2, 15, 13, 2, 13, 5, 2, 5, 9, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 1, 10, 0, 10, 15, 0, 15, 13, 0, 13, 5, 0, 5, 9, 0, 9, 2, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 10, 15, 1, 15, 13, 1, 13, 5, 1, 5, 9, 1, 9, 2, 1, 2, 6, 1, 6, 14, 1, 14, 12, -1, -1, -1,
+        -1,
+    ],
+    [
+        1, 12, 13, 1, 13, 5, 1, 5, 9, 1, 9, 2, 1, 2, 8, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 6, 14, 0, 14, 13, 0, 13, 5, 0, 5, 9, 0, 9, 2, 0, 2, 8, 0, 8, 1, -1, -1, -1, -1, -1, -1,
+        -1,
+    ],
+    [
+        0, 12, 13, 0, 13, 5, 0, 5, 9, 0, 9, 2, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        2, 6, 14, 2, 14, 13, 2, 13, 5, 2, 5, 9, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        5, 9, 7, 5, 7, 6, 5, 6, 8, 5, 8, 10, 5, 10, 15, 5, 15, 13, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 8, 10, 0, 10, 15, 0, 15, 13, 0, 13, 5, 0, 5, 9, 0, 9, 7, 0, 7, 14, 0, 14, 12, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 1, 10, 0, 10, 15, 0, 15, 13, 0, 13, 5, 0, 5, 9, 0, 9, 7, 0, 7, 6, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 10, 15, 1, 15, 13, 1, 13, 5, 1, 5, 9, 1, 9, 7, 1, 7, 14, 1, 14, 12, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 12, 13, 1, 13, 5, 1, 5, 9, 1, 9, 7, 1, 7, 6, 1, 6, 8, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 8, 1, 5, 9, 7, 5, 7, 14, 5, 14, 13, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 12, 13, 0, 13, 5, 0, 5, 9, 0, 9, 7, 0, 7, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        5, 9, 7, 5, 7, 14, 5, 14, 13, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        4, 5, 11, 4, 11, 15, 4, 15, 14, 4, 14, 7, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 6, 7, 0, 7, 4, 0, 4, 5, 0, 5, 11, 0, 11, 15, 0, 15, 12, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 1, 8, 4, 5, 11, 4, 11, 15, 4, 15, 14, 4, 14, 7, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        1, 8, 6, 1, 6, 7, 1, 7, 4, 1, 4, 5, 1, 5, 11, 1, 11, 15, 1, 15, 12, -1, -1, -1, -1, -1, -1,
+        -1,
+    ],
+    [
+        1, 12, 14, 1, 14, 7, 1, 7, 4, 1, 4, 5, 1, 5, 11, 1, 11, 10, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 6, 7, 0, 7, 4, 0, 4, 5, 0, 5, 11, 0, 11, 10, 0, 10, 1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 12, 14, 0, 14, 7, 0, 7, 4, 0, 4, 5, 0, 5, 11, 0, 11, 10, 0, 10, 8, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        4, 5, 11, 4, 11, 10, 4, 10, 8, 4, 8, 6, 4, 6, 7, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        2, 4, 5, 2, 5, 11, 2, 11, 15, 2, 15, 14, 2, 14, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 2, 4, 0, 4, 5, 0, 5, 11, 0, 11, 15, 0, 15, 12, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 1, 8, 2, 4, 5, 2, 5, 11, 2, 11, 15, 2, 15, 14, 2, 14, 6, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 8, 2, 1, 2, 4, 1, 4, 5, 1, 5, 11, 1, 11, 15, 1, 15, 12, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 12, 14, 1, 14, 6, 1, 6, 2, 1, 2, 4, 1, 4, 5, 1, 5, 11, 1, 11, 10, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 2, 4, 0, 4, 5, 0, 5, 11, 0, 11, 10, 0, 10, 1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 12, 14, 0, 14, 6, 0, 6, 2, 0, 2, 4, 0, 4, 5, 0, 5, 11, 0, 11, 10, 0, 10, 8, -1, -1, -1,
+        -1,
+    ],
+    [
+        2, 4, 5, 2, 5, 11, 2, 11, 10, 2, 10, 8, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        2, 8, 3, 2, 3, 9, 4, 5, 11, 4, 11, 15, 4, 15, 14, 4, 14, 7, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 6, 7, 0, 7, 4, 0, 4, 5, 0, 5, 11, 0, 11, 15, 0, 15, 12, 2, 8, 3, 2, 3, 9, -1, -1, -1, -1,
+    ],
+    [
+        0, 1, 3, 0, 3, 9, 0, 9, 2, 4, 5, 11, 4, 11, 15, 4, 15, 14, 4, 14, 7, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 3, 9, 1, 9, 2, 1, 2, 6, 1, 6, 7, 1, 7, 4, 1, 4, 5, 1, 5, 11, 1, 11, 15, 1, 15, 12, -1,
+    ],
+    [
+        1, 12, 14, 1, 14, 7, 1, 7, 4, 1, 4, 5, 1, 5, 11, 1, 11, 10, 2, 8, 3, 2, 3, 9, -1, -1, -1,
+        -1,
+    ],
+    [
+        0, 6, 7, 0, 7, 4, 0, 4, 5, 0, 5, 11, 0, 11, 10, 0, 10, 1, 2, 8, 3, 2, 3, 9, -1, -1, -1, -1,
+    ],
+    [
+        0, 12, 14, 0, 14, 7, 0, 7, 4, 0, 4, 5, 0, 5, 11, 0, 11, 10, 0, 10, 3, 0, 3, 9, 0, 9, 2, -1,
+    ],
+    [
+        2, 6, 7, 2, 7, 4, 2, 4, 5, 2, 5, 11, 2, 11, 10, 2, 10, 3, 2, 3, 9, -1, -1, -1, -1, -1, -1,
+        -1,
+    ],
+    [
+        3, 9, 4, 3, 4, 5, 3, 5, 11, 3, 11, 15, 3, 15, 14, 3, 14, 6, 3, 6, 8, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 8, 3, 0, 3, 9, 0, 9, 4, 0, 4, 5, 0, 5, 11, 0, 11, 15, 0, 15, 12, -1, -1, -1, -1, -1, -1,
+        -1,
+    ],
+    [
+        0, 1, 3, 0, 3, 9, 0, 9, 4, 0, 4, 5, 0, 5, 11, 0, 11, 15, 0, 15, 14, 0, 14, 6, -1, -1, -1,
+        -1,
+    ],
+    [
+        1, 3, 9, 1, 9, 4, 1, 4, 5, 1, 5, 11, 1, 11, 15, 1, 15, 12, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 12, 14, 1, 14, 6, 1, 6, 8, 1, 8, 3, 1, 3, 9, 1, 9, 4, 1, 4, 5, 1, 5, 11, 1, 11, 10, -1,
+    ],
+    [
+        0, 8, 3, 0, 3, 9, 0, 9, 4, 0, 4, 5, 0, 5, 11, 0, 11, 10, 0, 10, 1, -1, -1, -1, -1, -1, -1,
+        -1,
+    ],
+    [
+        0, 12, 14, 0, 14, 6, 3, 9, 4, 3, 4, 5, 3, 5, 11, 3, 11, 10, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        3, 9, 4, 3, 4, 5, 3, 5, 11, 3, 11, 10, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        3, 10, 15, 3, 15, 14, 3, 14, 7, 3, 7, 4, 3, 4, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 6, 7, 0, 7, 4, 0, 4, 5, 0, 5, 3, 0, 3, 10, 0, 10, 15, 0, 15, 12, -1, -1, -1, -1, -1, -1,
+        -1,
+    ],
+    [
+        0, 1, 8, 3, 10, 15, 3, 15, 14, 3, 14, 7, 3, 7, 4, 3, 4, 5, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 8, 6, 1, 6, 7, 1, 7, 4, 1, 4, 5, 1, 5, 3, 1, 3, 10, 1, 10, 15, 1, 15, 12, -1, -1, -1, -1,
+    ],
+    [
+        1, 12, 14, 1, 14, 7, 1, 7, 4, 1, 4, 5, 1, 5, 3, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 6, 7, 0, 7, 4, 0, 4, 5, 0, 5, 3, 0, 3, 1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 12, 14, 0, 14, 7, 0, 7, 4, 0, 4, 5, 0, 5, 3, 0, 3, 8, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        3, 8, 6, 3, 6, 7, 3, 7, 4, 3, 4, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        2, 4, 5, 2, 5, 3, 2, 3, 10, 2, 10, 15, 2, 15, 14, 2, 14, 6, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 2, 4, 0, 4, 5, 0, 5, 3, 0, 3, 10, 0, 10, 15, 0, 15, 12, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 1, 8, 2, 4, 5, 2, 5, 3, 2, 3, 10, 2, 10, 15, 2, 15, 14, 2, 14, 6, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 8, 2, 1, 2, 4, 1, 4, 5, 1, 5, 3, 1, 3, 10, 1, 10, 15, 1, 15, 12, -1, -1, -1, -1, -1, -1,
+        -1,
+    ],
+    [
+        1, 12, 14, 1, 14, 6, 1, 6, 2, 1, 2, 4, 1, 4, 5, 1, 5, 3, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 2, 4, 0, 4, 5, 0, 5, 3, 0, 3, 1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 12, 14, 0, 14, 6, 0, 6, 2, 0, 2, 4, 0, 4, 5, 0, 5, 3, 0, 3, 8, -1, -1, -1, -1, -1, -1,
+        -1,
+    ],
+    [
+        2, 4, 5, 2, 5, 3, 2, 3, 8, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        2, 8, 10, 2, 10, 15, 2, 15, 14, 2, 14, 7, 2, 7, 4, 2, 4, 5, 2, 5, 9, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 6, 7, 0, 7, 4, 0, 4, 5, 0, 5, 9, 0, 9, 2, 0, 2, 8, 0, 8, 10, 0, 10, 15, 0, 15, 12, -1,
+    ],
+    [
+        0, 1, 10, 0, 10, 15, 0, 15, 14, 0, 14, 7, 0, 7, 4, 0, 4, 5, 0, 5, 9, 0, 9, 2, -1, -1, -1,
+        -1,
+    ],
+    [
+        1, 10, 15, 1, 15, 12, 2, 6, 7, 2, 7, 4, 2, 4, 5, 2, 5, 9, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 12, 14, 1, 14, 7, 1, 7, 4, 1, 4, 5, 1, 5, 9, 1, 9, 2, 1, 2, 8, -1, -1, -1, -1, -1, -1,
+        -1,
+    ],
+    [
+        0, 6, 7, 0, 7, 4, 0, 4, 5, 0, 5, 9, 0, 9, 2, 0, 2, 8, 0, 8, 1, -1, -1, -1, -1, -1, -1, -1,
+    ],
+    [
+        0, 12, 14, 0, 14, 7, 0, 7, 4, 0, 4, 5, 0, 5, 9, 0, 9, 2, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        2, 6, 7, 2, 7, 4, 2, 4, 5, 2, 5, 9, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        4, 5, 9, 6, 8, 10, 6, 10, 15, 6, 15, 14, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 8, 10, 0, 10, 15, 0, 15, 12, 4, 5, 9, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 1, 10, 0, 10, 15, 0, 15, 14, 0, 14, 6, 4, 5, 9, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        1, 10, 15, 1, 15, 12, 4, 5, 9, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        1, 12, 14, 1, 14, 6, 1, 6, 8, 4, 5, 9, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 8, 1, 4, 5, 9, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 12, 14, 0, 14, 6, 4, 5, 9, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        4, 5, 9, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1, -1,
+    ],
+    [
+        4, 9, 11, 4, 11, 15, 4, 15, 13, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 6, 14, 0, 14, 12, 4, 9, 11, 4, 11, 15, 4, 15, 13, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 1, 8, 4, 9, 11, 4, 11, 15, 4, 15, 13, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        1, 8, 6, 1, 6, 14, 1, 14, 12, 4, 9, 11, 4, 11, 15, 4, 15, 13, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        1, 12, 13, 1, 13, 4, 1, 4, 9, 1, 9, 11, 1, 11, 10, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 6, 14, 0, 14, 13, 0, 13, 4, 0, 4, 9, 0, 9, 11, 0, 11, 10, 0, 10, 1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 12, 13, 0, 13, 4, 0, 4, 9, 0, 9, 11, 0, 11, 10, 0, 10, 8, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        4, 9, 11, 4, 11, 10, 4, 10, 8, 4, 8, 6, 4, 6, 14, 4, 14, 13, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        2, 7, 6, 4, 9, 11, 4, 11, 15, 4, 15, 13, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 2, 7, 0, 7, 14, 0, 14, 12, 4, 9, 11, 4, 11, 15, 4, 15, 13, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 1, 8, 2, 7, 6, 4, 9, 11, 4, 11, 15, 4, 15, 13, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        1, 8, 2, 1, 2, 7, 1, 7, 14, 1, 14, 12, 4, 9, 11, 4, 11, 15, 4, 15, 13, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 12, 13, 1, 13, 4, 1, 4, 9, 1, 9, 11, 1, 11, 10, 2, 7, 6, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 2, 7, 0, 7, 14, 0, 14, 13, 0, 13, 4, 0, 4, 9, 0, 9, 11, 0, 11, 10, 0, 10, 1, -1, -1, -1,
+        -1,
+    ],
+    [
+        0, 12, 13, 0, 13, 4, 0, 4, 9, 0, 9, 11, 0, 11, 10, 0, 10, 8, 2, 7, 6, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        2, 7, 14, 2, 14, 13, 2, 13, 4, 2, 4, 9, 2, 9, 11, 2, 11, 10, 2, 10, 8, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        2, 8, 3, 2, 3, 11, 2, 11, 15, 2, 15, 13, 2, 13, 4, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 6, 14, 0, 14, 12, 2, 8, 3, 2, 3, 11, 2, 11, 15, 2, 15, 13, 2, 13, 4, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 1, 3, 0, 3, 11, 0, 11, 15, 0, 15, 13, 0, 13, 4, 0, 4, 2, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 3, 11, 1, 11, 15, 1, 15, 13, 1, 13, 4, 1, 4, 2, 1, 2, 6, 1, 6, 14, 1, 14, 12, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 12, 13, 1, 13, 4, 1, 4, 2, 1, 2, 8, 1, 8, 3, 1, 3, 11, 1, 11, 10, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 6, 14, 0, 14, 13, 0, 13, 4, 0, 4, 2, 0, 2, 8, 0, 8, 3, 0, 3, 11, 0, 11, 10, 0, 10, 1, -1,
+    ],
+    [
+        0, 12, 13, 0, 13, 4, 0, 4, 2, 3, 11, 10, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        2, 6, 14, 2, 14, 13, 2, 13, 4, 3, 11, 10, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        3, 11, 15, 3, 15, 13, 3, 13, 4, 3, 4, 7, 3, 7, 6, 3, 6, 8, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 8, 3, 0, 3, 11, 0, 11, 15, 0, 15, 13, 0, 13, 4, 0, 4, 7, 0, 7, 14, 0, 14, 12, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 1, 3, 0, 3, 11, 0, 11, 15, 0, 15, 13, 0, 13, 4, 0, 4, 7, 0, 7, 6, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 3, 11, 1, 11, 15, 1, 15, 13, 1, 13, 4, 1, 4, 7, 1, 7, 14, 1, 14, 12, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 12, 13, 1, 13, 4, 1, 4, 7, 1, 7, 6, 1, 6, 8, 1, 8, 3, 1, 3, 11, 1, 11, 10, -1, -1, -1,
+        -1,
+    ],
+    [
+        0, 8, 3, 0, 3, 11, 0, 11, 10, 0, 10, 1, 4, 7, 14, 4, 14, 13, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 12, 13, 0, 13, 4, 0, 4, 7, 0, 7, 6, 3, 11, 10, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        3, 11, 10, 4, 7, 14, 4, 14, 13, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        3, 10, 15, 3, 15, 13, 3, 13, 4, 3, 4, 9, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 6, 14, 0, 14, 12, 3, 10, 15, 3, 15, 13, 3, 13, 4, 3, 4, 9, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 1, 8, 3, 10, 15, 3, 15, 13, 3, 13, 4, 3, 4, 9, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        1, 8, 6, 1, 6, 14, 1, 14, 12, 3, 10, 15, 3, 15, 13, 3, 13, 4, 3, 4, 9, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 12, 13, 1, 13, 4, 1, 4, 9, 1, 9, 3, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 6, 14, 0, 14, 13, 0, 13, 4, 0, 4, 9, 0, 9, 3, 0, 3, 1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 12, 13, 0, 13, 4, 0, 4, 9, 0, 9, 3, 0, 3, 8, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        3, 8, 6, 3, 6, 14, 3, 14, 13, 3, 13, 4, 3, 4, 9, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        2, 7, 6, 3, 10, 15, 3, 15, 13, 3, 13, 4, 3, 4, 9, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 2, 7, 0, 7, 14, 0, 14, 12, 3, 10, 15, 3, 15, 13, 3, 13, 4, 3, 4, 9, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 1, 8, 2, 7, 6, 3, 10, 15, 3, 15, 13, 3, 13, 4, 3, 4, 9, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 8, 2, 1, 2, 7, 1, 7, 14, 1, 14, 12, 3, 10, 15, 3, 15, 13, 3, 13, 4, 3, 4, 9, -1, -1, -1,
+        -1,
+    ],
+    [
+        1, 12, 13, 1, 13, 4, 1, 4, 9, 1, 9, 3, 2, 7, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 2, 7, 0, 7, 14, 0, 14, 13, 0, 13, 4, 0, 4, 9, 0, 9, 3, 0, 3, 1, -1, -1, -1, -1, -1, -1,
+        -1,
+    ],
+    [
+        0, 12, 13, 0, 13, 4, 0, 4, 9, 0, 9, 3, 0, 3, 8, 2, 7, 6, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        2, 7, 14, 2, 14, 13, 2, 13, 4, 2, 4, 9, 2, 9, 3, 2, 3, 8, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        2, 8, 10, 2, 10, 15, 2, 15, 13, 2, 13, 4, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 6, 14, 0, 14, 12, 2, 8, 10, 2, 10, 15, 2, 15, 13, 2, 13, 4, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 1, 10, 0, 10, 15, 0, 15, 13, 0, 13, 4, 0, 4, 2, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        1, 10, 15, 1, 15, 13, 1, 13, 4, 1, 4, 2, 1, 2, 6, 1, 6, 14, 1, 14, 12, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 12, 13, 1, 13, 4, 1, 4, 2, 1, 2, 8, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 6, 14, 0, 14, 13, 0, 13, 4, 0, 4, 2, 0, 2, 8, 0, 8, 1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 12, 13, 0, 13, 4, 0, 4, 2, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        2, 6, 14, 2, 14, 13, 2, 13, 4, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        4, 7, 6, 4, 6, 8, 4, 8, 10, 4, 10, 15, 4, 15, 13, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 8, 10, 0, 10, 15, 0, 15, 13, 0, 13, 4, 0, 4, 7, 0, 7, 14, 0, 14, 12, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 1, 10, 0, 10, 15, 0, 15, 13, 0, 13, 4, 0, 4, 7, 0, 7, 6, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 10, 15, 1, 15, 13, 1, 13, 4, 1, 4, 7, 1, 7, 14, 1, 14, 12, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        1, 12, 13, 1, 13, 4, 1, 4, 7, 1, 7, 6, 1, 6, 8, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 8, 1, 4, 7, 14, 4, 14, 13, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 12, 13, 0, 13, 4, 0, 4, 7, 0, 7, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        4, 7, 14, 4, 14, 13, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1, -1,
+    ],
+    [
+        7, 9, 11, 7, 11, 15, 7, 15, 14, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 6, 7, 0, 7, 9, 0, 9, 11, 0, 11, 15, 0, 15, 12, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 1, 8, 7, 9, 11, 7, 11, 15, 7, 15, 14, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        1, 8, 6, 1, 6, 7, 1, 7, 9, 1, 9, 11, 1, 11, 15, 1, 15, 12, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 12, 14, 1, 14, 7, 1, 7, 9, 1, 9, 11, 1, 11, 10, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 6, 7, 0, 7, 9, 0, 9, 11, 0, 11, 10, 0, 10, 1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 12, 14, 0, 14, 7, 0, 7, 9, 0, 9, 11, 0, 11, 10, 0, 10, 8, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        6, 7, 9, 6, 9, 11, 6, 11, 10, 6, 10, 8, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        2, 9, 11, 2, 11, 15, 2, 15, 14, 2, 14, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 2, 9, 0, 9, 11, 0, 11, 15, 0, 15, 12, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 1, 8, 2, 9, 11, 2, 11, 15, 2, 15, 14, 2, 14, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        1, 8, 2, 1, 2, 9, 1, 9, 11, 1, 11, 15, 1, 15, 12, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        1, 12, 14, 1, 14, 6, 1, 6, 2, 1, 2, 9, 1, 9, 11, 1, 11, 10, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 2, 9, 0, 9, 11, 0, 11, 10, 0, 10, 1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 12, 14, 0, 14, 6, 0, 6, 2, 0, 2, 9, 0, 9, 11, 0, 11, 10, 0, 10, 8, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        2, 9, 11, 2, 11, 10, 2, 10, 8, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        2, 8, 3, 2, 3, 11, 2, 11, 15, 2, 15, 14, 2, 14, 7, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 6, 7, 0, 7, 2, 0, 2, 8, 0, 8, 3, 0, 3, 11, 0, 11, 15, 0, 15, 12, -1, -1, -1, -1, -1, -1,
+        -1,
+    ],
+    [
+        0, 1, 3, 0, 3, 11, 0, 11, 15, 0, 15, 14, 0, 14, 7, 0, 7, 2, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 3, 11, 1, 11, 15, 1, 15, 12, 2, 6, 7, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        1, 12, 14, 1, 14, 7, 1, 7, 2, 1, 2, 8, 1, 8, 3, 1, 3, 11, 1, 11, 10, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 6, 7, 0, 7, 2, 0, 2, 8, 0, 8, 3, 0, 3, 11, 0, 11, 10, 0, 10, 1, -1, -1, -1, -1, -1, -1,
+        -1,
+    ],
+    [
+        0, 12, 14, 0, 14, 7, 0, 7, 2, 3, 11, 10, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        2, 6, 7, 3, 11, 10, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        3, 11, 15, 3, 15, 14, 3, 14, 6, 3, 6, 8, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 8, 3, 0, 3, 11, 0, 11, 15, 0, 15, 12, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 1, 3, 0, 3, 11, 0, 11, 15, 0, 15, 14, 0, 14, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        1, 3, 11, 1, 11, 15, 1, 15, 12, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        1, 12, 14, 1, 14, 6, 1, 6, 8, 1, 8, 3, 1, 3, 11, 1, 11, 10, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 8, 3, 0, 3, 11, 0, 11, 10, 0, 10, 1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 12, 14, 0, 14, 6, 3, 11, 10, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        3, 11, 10, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1, -1,
+    ],
+    [
+        3, 10, 15, 3, 15, 14, 3, 14, 7, 3, 7, 9, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 6, 7, 0, 7, 9, 0, 9, 3, 0, 3, 10, 0, 10, 15, 0, 15, 12, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 1, 8, 3, 10, 15, 3, 15, 14, 3, 14, 7, 3, 7, 9, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        1, 8, 6, 1, 6, 7, 1, 7, 9, 1, 9, 3, 1, 3, 10, 1, 10, 15, 1, 15, 12, -1, -1, -1, -1, -1, -1,
+        -1,
+    ],
+    [
+        1, 12, 14, 1, 14, 7, 1, 7, 9, 1, 9, 3, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 6, 7, 0, 7, 9, 0, 9, 3, 0, 3, 1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 12, 14, 0, 14, 7, 0, 7, 9, 0, 9, 3, 0, 3, 8, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        3, 8, 6, 3, 6, 7, 3, 7, 9, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        2, 9, 3, 2, 3, 10, 2, 10, 15, 2, 15, 14, 2, 14, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 2, 9, 0, 9, 3, 0, 3, 10, 0, 10, 15, 0, 15, 12, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 1, 8, 2, 9, 3, 2, 3, 10, 2, 10, 15, 2, 15, 14, 2, 14, 6, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 8, 2, 1, 2, 9, 1, 9, 3, 1, 3, 10, 1, 10, 15, 1, 15, 12, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        1, 12, 14, 1, 14, 6, 1, 6, 2, 1, 2, 9, 1, 9, 3, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 2, 9, 0, 9, 3, 0, 3, 1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 12, 14, 0, 14, 6, 0, 6, 2, 0, 2, 9, 0, 9, 3, 0, 3, 8, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        2, 9, 3, 2, 3, 8, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        2, 8, 10, 2, 10, 15, 2, 15, 14, 2, 14, 7, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 6, 7, 0, 7, 2, 0, 2, 8, 0, 8, 10, 0, 10, 15, 0, 15, 12, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 1, 10, 0, 10, 15, 0, 15, 14, 0, 14, 7, 0, 7, 2, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        1, 10, 15, 1, 15, 12, 2, 6, 7, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        1, 12, 14, 1, 14, 7, 1, 7, 2, 1, 2, 8, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1,
+    ],
+    [
+        0, 6, 7, 0, 7, 2, 0, 2, 8, 0, 8, 1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1,
+    ],
+    [
+        0, 12, 14, 0, 14, 7, 0, 7, 2, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        2, 6, 7, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1, -1,
+    ],
+    [
+        6, 8, 10, 6, 10, 15, 6, 15, 14, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 8, 10, 0, 10, 15, 0, 15, 12, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 1, 10, 0, 10, 15, 0, 15, 14, 0, 14, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        1, 10, 15, 1, 15, 12, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1, -1,
+    ],
+    [
+        1, 12, 14, 1, 14, 6, 1, 6, 8, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1,
+    ],
+    [
+        0, 8, 1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1, -1,
+    ],
+    [
+        0, 12, 14, 0, 14, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1, -1,
+    ],
+    [
+        -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        -1, -1, -1, -1, -1,
+    ],
+];
+
+#[cfg(test)]
+mod tests {
+    use super::{TRANSITION_EDGES, TRANSITION_SAMPLES, TRANSITION_TRIANGLES};
+    use std::collections::BTreeMap;
+
+    // The faces of a transition cell, as loops of samples. Each of the 4 sides joins the
+    // high-resolution face to the low-resolution face.
+    const FACES: [&[usize]; 9] = [
+        &[0, 1, 4, 3],
+        &[1, 2, 5, 4],
+        &[3, 4, 7, 6],
+        &[4, 5, 8, 7],
+        &[9, 10, 12, 11],
+        &[0, 1, 2, 10, 9],
+        &[6, 7, 8, 12, 11],
+        &[0, 3, 6, 11, 9],
+        &[2, 5, 8, 12, 10],
+    ];
+
+    // The position of a sample, with the high-resolution face at z = 0, and the low-resolution
+    // face at z = 1
+    fn position(sample: usize) -> [f32; 3] {
+        let [i, j] = TRANSITION_SAMPLES[sample];
+        [i as f32, j as f32, if sample < 9 { 0.0 } else { 1.0 }]
+    }
+
+    // The samples of a face, wound counter-clockwise when viewed from outside the cell
+    fn outward(face: &[usize]) -> Vec<usize> {
+        // Newell's method finds the normal of the polygon as wound
+        let mut normal = [0.0; 3];
+        let mut centre = [0.0; 3];
+        for (k, &sample) in face.iter().enumerate() {
+            let a = position(sample);
+            let b = position(face[(k + 1) % face.len()]);
+            normal[0] += (a[1] - b[1]) * (a[2] + b[2]);
+            normal[1] += (a[2] - b[2]) * (a[0] + b[0]);
+            normal[2] += (a[0] - b[0]) * (a[1] + b[1]);
+            for axis in 0..3 {
+                centre[axis] += a[axis] / face.len() as f32;
+            }
+        }
+
+        let out = [centre[0] - 1.0, centre[1] - 1.0, centre[2] - 0.5];
+        let mut face = face.to_vec();
+        if (0..3).map(|axis| out[axis] * normal[axis]).sum::<f32>() < 0.0 {
+            face.reverse();
+        }
+        face
+    }
+
+    fn edge(a: usize, b: usize) -> usize {
+        TRANSITION_EDGES
+            .iter()
+            .position(|&edge| edge == [a, b] || edge == [b, a])
+            .unwrap()
+    }
+
+    fn generate(case: usize) -> [i8; 28] {
+        // Samples on the low-resolution face share the values of the corners of the cell
+        let inside = |sample: usize| {
+            let sample = if sample < 9 {
+                sample
+            } else {
+                [0, 2, 6, 8][sample - 9]
+            };
+            case & (1 << sample) != 0
+        };
+
+        // Each face contributes a segment of contour from every edge where its loop enters the
+        // inside, to the next edge where it leaves
+        let mut next = BTreeMap::new();
+        for face in FACES.iter() {
+            let face = outward(face);
+            let mut crossings = vec![];
+            for (k, &a) in face.iter().enumerate() {
+                let b = face[(k + 1) % face.len()];
+                if inside(a) != inside(b) {
+                    crossings.push((edge(a, b), inside(b)));
+                }
+            }
+            if let Some(first) = crossings.iter().position(|&(_, enters)| enters) {
+                crossings.rotate_left(first);
+            }
+            for pair in crossings.chunks(2) {
+                next.insert(pair[0].0, pair[1].0);
+            }
+        }
+
+        // Follow the segments into loops, and fan each loop from its first edge
+        let mut triangles = vec![];
+        let mut visited = vec![];
+        for &start in next.keys() {
+            if visited.contains(&start) {
+                continue;
+            }
+            let mut contour = vec![start];
+            let mut edge = next[&start];
+            while edge != start {
+                contour.push(edge);
+                edge = next[&edge];
+            }
+            for k in 1..contour.len() - 1 {
+                triangles.extend_from_slice(&[contour[0], contour[k], contour[k + 1]]);
+            }
+            visited.extend(contour);
+        }
+
+        let mut result = [-1; 28];
+        for (entry, &edge) in result.iter_mut().zip(&triangles) {
+            *entry = edge as i8;
+        }
+        assert!(triangles.len() <= result.len());
+        result
+    }
+
+    #[test]
+    fn transition_table_matches_generator() {
+        for (case, triangles) in TRANSITION_TRIANGLES.iter().enumerate() {
+            assert_eq!(*triangles, generate(case), "case {}", case);
+        }
+    }
+}
@@ -0,0 +1,248 @@
+// Copyright 2018 Tristam MacDonald
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use math::Vec3;
+use region::Region;
+use source::{HermiteSource, Source};
+
+/// How a [`VoxelGrid`](struct.VoxelGrid.html) reconstructs values between its voxels
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Interpolation {
+    /// Blend the 8 surrounding voxels linearly. Fast, but the gradient is discontinuous between
+    /// cells, which shows up as faceting in the normals.
+    Trilinear,
+    /// Blend the 64 surrounding voxels with Catmull-Rom splines. Slower, but the result and its
+    /// gradient are continuous.
+    Tricubic,
+}
+
+/// A source backed by a dense grid of voxels, such as a scanned volume or the output of a
+/// simulation.
+///
+/// Voxels are laid out over a [`Region`](../region/struct.Region.html), with the first voxel at the
+/// origin of the region and the last at the opposite corner. Values between voxels are
+/// interpolated, and normals are the exact gradient of that interpolation. Outside the region the
+/// values of the nearest voxels on its boundary are extended indefinitely.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VoxelGrid {
+    region: Region,
+    step: Vec3,
+    interpolation: Interpolation,
+    data: Vec<f32>,
+}
+
+impl VoxelGrid {
+    /// Create a VoxelGrid covering the given region, from voxels laid out in x, then y, then z
+    /// order, with trilinear interpolation.
+    ///
+    /// Panics if the region has fewer than 2 samples along any axis, or if the length of `data`
+    /// does not match the number of samples in the region.
+    pub fn new(region: Region, data: Vec<f32>) -> VoxelGrid {
+        assert_interpolable(&region);
+        let [size_x, size_y, size_z] = region.samples;
+        assert_eq!(
+            data.len(),
+            size_x * size_y * size_z,
+            "voxel data does not match the size of the region"
+        );
+        VoxelGrid {
+            region,
+            step: region.step(),
+            interpolation: Interpolation::Trilinear,
+            data,
+        }
+    }
+
+    /// Create a VoxelGrid covering the given region, by sampling the given
+    /// [`Source`](../source/trait.Source.html) at each voxel
+    ///
+    /// Panics if the region has fewer than 2 samples along any axis.
+    pub fn from_source<S>(region: Region, source: &S) -> VoxelGrid
+    where
+        S: Source,
+    {
+        assert_interpolable(&region);
+        let [size_x, size_y, size_z] = region.samples;
+        let mut data = Vec::with_capacity(size_x * size_y * size_z);
+        for z in 0..size_z {
+            for y in 0..size_y {
+                for x in 0..size_x {
+                    let p = region.position(x, y, z);
+                    data.push(source.sample(p.x, p.y, p.z));
+                }
+            }
+        }
+        VoxelGrid::new(region, data)
+    }
+
+    /// Change how values between voxels are reconstructed
+    pub fn set_interpolation(&mut self, interpolation: Interpolation) {
+        self.interpolation = interpolation;
+    }
+
+    /// How values between voxels are reconstructed
+    pub fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+
+    /// The region covered by the voxels
+    pub fn region(&self) -> Region {
+        self.region
+    }
+
+    /// The voxels, laid out in x, then y, then z order
+    pub fn data(&self) -> &[f32] {
+        &self.data
+    }
+
+    /// Mutable access to the voxels, laid out in x, then y, then z order
+    pub fn data_mut(&mut self) -> &mut [f32] {
+        &mut self.data
+    }
+
+    /// The value of the voxel with the given integer coordinates
+    pub fn get(&self, x: usize, y: usize, z: usize) -> f32 {
+        let [size_x, size_y, _] = self.region.samples;
+        self.data[(z * size_y + y) * size_x + x]
+    }
+
+    // Interpolate the value at the given point, along with its gradient
+    fn interpolate(&self, x: f32, y: f32, z: f32) -> (f32, Vec3) {
+        let tx = self.taps(0, x);
+        let ty = self.taps(1, y);
+        let tz = self.taps(2, z);
+
+        let mut value = 0.0;
+        let mut gradient = Vec3::zero();
+
+        for k in 0..tz.count {
+            for j in 0..ty.count {
+                for i in 0..tx.count {
+                    let v = self.get(tx.indices[i], ty.indices[j], tz.indices[k]);
+                    value += tx.weights[i] * ty.weights[j] * tz.weights[k] * v;
+                    gradient.x += tx.derivatives[i] * ty.weights[j] * tz.weights[k] * v;
+                    gradient.y += tx.weights[i] * ty.derivatives[j] * tz.weights[k] * v;
+                    gradient.z += tx.weights[i] * ty.weights[j] * tz.derivatives[k] * v;
+                }
+            }
+        }
+
+        (value, gradient / self.step)
+    }
+
+    // The voxels along one axis which contribute to the given coordinate, and their weights
+    fn taps(&self, axis: usize, coordinate: f32) -> Taps {
+        let size = self.region.samples[axis];
+        let u = (coordinate - self.region.origin[axis]) / self.step[axis];
+        let u = u.max(0.0).min((size - 1) as f32);
+        let cell = (u.floor() as usize).min(size - 2);
+        let t = u - cell as f32;
+
+        let mut taps = Taps {
+            indices: [0; 4],
+            weights: [0.0; 4],
+            derivatives: [0.0; 4],
+            count: 0,
+        };
+
+        match self.interpolation {
+            Interpolation::Trilinear => {
+                taps.indices[0] = cell;
+                taps.indices[1] = cell + 1;
+                taps.weights[0] = 1.0 - t;
+                taps.weights[1] = t;
+                taps.derivatives[0] = -1.0;
+                taps.derivatives[1] = 1.0;
+                taps.count = 2;
+            }
+            Interpolation::Tricubic => {
+                for i in 0..4 {
+                    // Repeat the boundary voxels where the neighbourhood leaves the grid
+                    let index = (cell + i).max(1) - 1;
+                    taps.indices[i] = index.min(size - 1);
+                }
+
+                let t2 = t * t;
+                let t3 = t2 * t;
+                taps.weights = [
+                    0.5 * (-t3 + 2.0 * t2 - t),
+                    0.5 * (3.0 * t3 - 5.0 * t2 + 2.0),
+                    0.5 * (-3.0 * t3 + 4.0 * t2 + t),
+                    0.5 * (t3 - t2),
+                ];
+                taps.derivatives = [
+                    0.5 * (-3.0 * t2 + 4.0 * t - 1.0),
+                    0.5 * (9.0 * t2 - 10.0 * t),
+                    0.5 * (-9.0 * t2 + 8.0 * t + 1.0),
+                    0.5 * (3.0 * t2 - 2.0 * t),
+                ];
+                taps.count = 4;
+            }
+        }
+
+        taps
+    }
+}
+
+impl Source for VoxelGrid {
+    fn sample(&self, x: f32, y: f32, z: f32) -> f32 {
+        self.interpolate(x, y, z).0
+    }
+
+    fn sample_layer(&self, region: &Region, z: usize, values: &mut [f32]) {
+        let [size_x, size_y, _] = region.samples;
+        let layer_size = size_x * size_y;
+
+        // Extracting the grid at its own resolution needs no interpolation at all
+        if *region == self.region {
+            let start = z * layer_size;
+            values[..layer_size].copy_from_slice(&self.data[start..start + layer_size]);
+            return;
+        }
+
+        let origin = region.origin;
+        let step = region.step();
+        for y in 0..size_y {
+            for x in 0..size_x {
+                values[y * size_x + x] = self.sample(
+                    origin.x + x as f32 * step.x,
+                    origin.y + y as f32 * step.y,
+                    origin.z + z as f32 * step.z,
+                );
+            }
+        }
+    }
+}
+
+impl HermiteSource for VoxelGrid {
+    fn sample_normal(&self, x: f32, y: f32, z: f32) -> Vec3 {
+        self.interpolate(x, y, z).1.normalize()
+    }
+}
+
+struct Taps {
+    indices: [usize; 4],
+    weights: [f32; 4],
+    derivatives: [f32; 4],
+    count: usize,
+}
+
+// Voxels are interpolated between neighbours along every axis, and spaced by the step of the
+// region, which needs at least 2 samples along each axis
+fn assert_interpolable(region: &Region) {
+    assert!(
+        region.samples.iter().all(|&samples| samples >= 2),
+        "voxel grids need at least 2 samples along each axis"
+    );
+}
@@ -0,0 +1,44 @@
+// Copyright 2018 Tristam MacDonald
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+extern crate isosurface;
+
+mod common;
+
+use common::open_edges;
+use isosurface::chunks::{ChunkCoord, ChunkedWorld};
+use isosurface::math::Vec3;
+use isosurface::sdf::Sphere;
+use isosurface::transform::Translate;
+
+#[test]
+fn neighbouring_chunks_share_border_vertices() {
+    // A chunk size which isn't a whole number of steps in floating point, with borders where
+    // regions computed independently for each chunk would sample slightly different points
+    let mut world = ChunkedWorld::new(0.7, 13);
+    for &x in &[-2, 7] {
+        // A sphere lying across the border, but within the pair of chunks either side of it
+        let border = world.region(ChunkCoord::new(x, 0, 0)).origin;
+        let source = Translate::new(Sphere::new(0.3), border + Vec3::new(0.0, 0.35, 0.35));
+
+        let meshes: Vec<_> = [ChunkCoord::new(x - 1, 0, 0), ChunkCoord::new(x, 0, 0)]
+            .iter()
+            .map(|&chunk| {
+                let mesh = world.extract(&source, chunk);
+                (mesh.vertices.clone(), mesh.indices.clone())
+            })
+            .collect();
+        assert!(meshes.iter().all(|(_, indices)| !indices.is_empty()));
+        assert_eq!(open_edges(&meshes), 0, "border before chunk {}", x);
+    }
+}
@@ -0,0 +1,101 @@
+// Copyright 2018 Tristam MacDonald
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Each test only uses some of these helpers
+#![allow(dead_code)]
+
+use isosurface::source::Source;
+use std::collections::HashMap;
+
+/// Welds the vertices of several meshes by position, and counts the edges which aren't matched
+/// by an edge running the other way. Closed, consistently wound meshes have none.
+pub fn open_edges(meshes: &[(Vec<f32>, Vec<u32>)]) -> usize {
+    let mut ids = HashMap::new();
+    let mut edges = HashMap::new();
+    for (vertices, indices) in meshes {
+        let welded: Vec<usize> = vertices
+            .chunks(3)
+            .map(|p| {
+                let key = [p[0].to_bits(), p[1].to_bits(), p[2].to_bits()];
+                let next = ids.len();
+                *ids.entry(key).or_insert(next)
+            })
+            .collect();
+        for triangle in indices.chunks(3) {
+            for i in 0..3 {
+                let a = welded[triangle[i] as usize];
+                let b = welded[triangle[(i + 1) % 3] as usize];
+                if a < b {
+                    *edges.entry((a, b)).or_insert(0) += 1;
+                } else if b < a {
+                    *edges.entry((b, a)).or_insert(0) -= 1;
+                }
+            }
+        }
+    }
+    edges.values().filter(|&&count: &&i32| count != 0).count()
+}
+
+/// Counts the edges of an indexed mesh which aren't used exactly once in each direction. Each
+/// such edge is either open, or shared by more than two triangles.
+pub fn non_manifold_edges(indices: &[u32]) -> usize {
+    let mut edges = HashMap::new();
+    for triangle in indices.chunks(3) {
+        for i in 0..3 {
+            *edges
+                .entry((triangle[i], triangle[(i + 1) % 3]))
+                .or_insert(0) += 1;
+        }
+    }
+    edges
+        .iter()
+        .filter(|&(&(a, b), &count)| count != 1 || edges.get(&(b, a)) != Some(&1))
+        .count()
+}
+
+/// The number of samples along each axis of the unit cube, for `Scattered`
+pub const SCATTERED_SAMPLES: usize = 16;
+
+/// Uncorrelated values at every sample of the unit cube, which exercise every ambiguous face and
+/// interior. Samples on the boundary of the cube are outside, so the surface is closed.
+pub struct Scattered {
+    pub seed: u32,
+}
+
+impl Scattered {
+    fn value(&self, x: usize, y: usize, z: usize) -> f32 {
+        if [x, y, z]
+            .iter()
+            .any(|&i| i == 0 || i == SCATTERED_SAMPLES - 1)
+        {
+            return 1.0;
+        }
+        let mut h = self.seed ^ (x as u32).wrapping_mul(0x9e37_79b1);
+        h = (h ^ (y as u32).wrapping_mul(0x85eb_ca77)).rotate_left(13);
+        h = (h ^ (z as u32).wrapping_mul(0xc2b2_ae3d)).wrapping_mul(0x27d4_eb2f);
+        h ^= h >> 15;
+        (h >> 8) as f32 / (1 << 23) as f32 - 1.0
+    }
+}
+
+impl Source for Scattered {
+    // Positions are mapped to the nearest sample of the unit cube
+    fn sample(&self, x: f32, y: f32, z: f32) -> f32 {
+        let index = |c: f32| {
+            let i = (c * (SCATTERED_SAMPLES - 1) as f32).round().max(0.0) as usize;
+            i.min(SCATTERED_SAMPLES - 1)
+        };
+        self.value(index(x), index(y), index(z))
+    }
+}
@@ -0,0 +1,226 @@
+// Copyright 2018 Tristam MacDonald
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+extern crate isosurface;
+
+use isosurface::export::{write_glb, GltfOptions, Material, VertexFormat};
+use isosurface::marching_cubes::MarchingCubes;
+use isosurface::sdf::Sphere;
+use isosurface::source::CentralDifference;
+use std::io::ErrorKind;
+
+fn u32_at(bytes: &[u8], offset: usize) -> usize {
+    let mut word = [0; 4];
+    word.copy_from_slice(&bytes[offset..offset + 4]);
+    u32::from_le_bytes(word) as usize
+}
+
+// Checks that the text is a single well-formed JSON value, returning where it stopped if not
+fn check_json(text: &[u8]) -> Result<(), usize> {
+    let mut position = 0;
+    json_value(text, &mut position)?;
+    skip_whitespace(text, &mut position);
+    if position == text.len() {
+        Ok(())
+    } else {
+        Err(position)
+    }
+}
+
+fn skip_whitespace(text: &[u8], position: &mut usize) {
+    while *position < text.len() && b" \t\r\n".contains(&text[*position]) {
+        *position += 1;
+    }
+}
+
+fn expect(text: &[u8], position: &mut usize, token: &[u8]) -> Result<(), usize> {
+    skip_whitespace(text, position);
+    if text[*position..].starts_with(token) {
+        *position += token.len();
+        Ok(())
+    } else {
+        Err(*position)
+    }
+}
+
+fn json_value(text: &[u8], position: &mut usize) -> Result<(), usize> {
+    skip_whitespace(text, position);
+    match text.get(*position) {
+        Some(b'{') => json_sequence(text, position, b'}', |text, position| {
+            json_string(text, position)?;
+            expect(text, position, b":")?;
+            json_value(text, position)
+        }),
+        Some(b'[') => json_sequence(text, position, b']', json_value),
+        Some(b'"') => json_string(text, position),
+        Some(b't') => expect(text, position, b"true"),
+        Some(b'f') => expect(text, position, b"false"),
+        Some(b'n') => expect(text, position, b"null"),
+        _ => json_number(text, position),
+    }
+}
+
+fn json_sequence<F>(
+    text: &[u8],
+    position: &mut usize,
+    close: u8,
+    mut element: F,
+) -> Result<(), usize>
+where
+    F: FnMut(&[u8], &mut usize) -> Result<(), usize>,
+{
+    *position += 1;
+    skip_whitespace(text, position);
+    if text.get(*position) == Some(&close) {
+        *position += 1;
+        return Ok(());
+    }
+    loop {
+        element(text, position)?;
+        skip_whitespace(text, position);
+        match text.get(*position) {
+            Some(&b',') => *position += 1,
+            Some(&c) if c == close => {
+                *position += 1;
+                return Ok(());
+            }
+            _ => return Err(*position),
+        }
+    }
+}
+
+fn json_string(text: &[u8], position: &mut usize) -> Result<(), usize> {
+    expect(text, position, b"\"")?;
+    while let Some(&c) = text.get(*position) {
+        *position += 1;
+        match c {
+            b'"' => return Ok(()),
+            b'\\' => *position += 1,
+            c if c < 0x20 => return Err(*position - 1),
+            _ => (),
+        }
+    }
+    Err(*position)
+}
+
+fn json_number(text: &[u8], position: &mut usize) -> Result<(), usize> {
+    let start = *position;
+    while *position < text.len() && b"+-.0123456789eE".contains(&text[*position]) {
+        *position += 1;
+    }
+    let number = std::str::from_utf8(&text[start..*position]).unwrap();
+    match number.parse::<f64>() {
+        Ok(value) if value.is_finite() && !number.starts_with('+') => Ok(()),
+        _ => Err(start),
+    }
+}
+
+fn sphere() -> (Vec<f32>, Vec<u32>) {
+    let mut vertices = vec![];
+    let mut indices = vec![];
+    MarchingCubes::new(16).extract_with_normals(
+        &CentralDifference::new(Sphere::new(0.4)),
+        &mut vertices,
+        &mut indices,
+    );
+    (vertices, indices)
+}
+
+#[test]
+fn glb_header_and_chunks_are_well_formed() {
+    let (vertices, indices) = sphere();
+    let colors: Vec<f32> = vertices.chunks(6).flat_map(|_| vec![0.5; 4]).collect();
+    let options = GltfOptions {
+        colors: Some(&colors),
+        material: Some(Material {
+            base_color: [0.8, 0.2, 0.1, 1.0],
+            metallic: 0.25,
+            roughness: 0.5,
+        }),
+    };
+    let mut glb = vec![];
+    write_glb(
+        &mut glb,
+        &vertices,
+        &indices,
+        VertexFormat::PositionsAndNormals,
+        &options,
+    )
+    .unwrap();
+
+    // The header gives the magic, the version, and the length of the whole file
+    assert_eq!(&glb[0..4], b"glTF");
+    assert_eq!(u32_at(&glb, 4), 2);
+    assert_eq!(u32_at(&glb, 8), glb.len());
+
+    // Followed by the JSON chunk, and the binary chunk, each padded to 4 bytes
+    let json_length = u32_at(&glb, 12);
+    assert_eq!(&glb[16..20], b"JSON");
+    assert_eq!(json_length % 4, 0);
+    let json = &glb[20..20 + json_length];
+    assert_eq!(check_json(json), Ok(()));
+
+    let binary = 20 + json_length;
+    let binary_length = u32_at(&glb, binary);
+    assert_eq!(&glb[binary + 4..binary + 8], b"BIN\0");
+    assert_eq!(binary_length % 4, 0);
+    assert_eq!(binary + 8 + binary_length, glb.len());
+}
+
+#[test]
+fn glb_rejects_values_json_cannot_represent() {
+    let (vertices, indices) = sphere();
+    let write = |vertices: &[f32], material: Material| {
+        let options = GltfOptions {
+            colors: None,
+            material: Some(material),
+        };
+        let mut glb = vec![];
+        let result = write_glb(
+            &mut glb,
+            vertices,
+            &indices,
+            VertexFormat::PositionsAndNormals,
+            &options,
+        );
+        // Nothing is written unless the whole file can be
+        result.map_err(|error| {
+            assert!(glb.is_empty());
+            error.kind()
+        })
+    };
+
+    let nan_color = Material {
+        base_color: [f32::NAN, 0.0, 0.0, 1.0],
+        ..Material::default()
+    };
+    let rough = Material {
+        roughness: 1.5,
+        ..Material::default()
+    };
+    let metallic = Material {
+        metallic: f32::INFINITY,
+        ..Material::default()
+    };
+    for &material in &[nan_color, rough, metallic] {
+        assert_eq!(write(&vertices, material), Err(ErrorKind::InvalidInput));
+    }
+
+    let mut infinite = vertices.clone();
+    infinite[0] = f32::INFINITY;
+    assert_eq!(
+        write(&infinite, Material::default()),
+        Err(ErrorKind::InvalidInput)
+    );
+    assert_eq!(write(&vertices, Material::default()), Ok(()));
+}
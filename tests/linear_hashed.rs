@@ -0,0 +1,80 @@
+// Copyright 2018 Tristam MacDonald
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+extern crate isosurface;
+
+mod common;
+
+use common::{non_manifold_edges, open_edges};
+use isosurface::linear_hashed_marching_cubes::LinearHashedMarchingCubes;
+use isosurface::math::Vec3;
+use isosurface::noise::{Fbm, Perlin};
+use isosurface::sdf::{Intersection, Sphere};
+use isosurface::source::Source;
+use isosurface::transform::Translate;
+
+// Samples a source at a higher frequency, without scaling the distances it returns
+struct Frequency<S> {
+    source: S,
+    frequency: f32,
+}
+
+impl<S: Source> Source for Frequency<S> {
+    fn sample(&self, x: f32, y: f32, z: f32) -> f32 {
+        let f = self.frequency;
+        self.source.sample(x * f, y * f, z * f)
+    }
+}
+
+// Noise clipped to a sphere, which refines to leaves of many different sizes
+fn clipped_noise(seed: u32) -> impl Source {
+    Intersection::new(
+        Frequency {
+            source: Fbm::new(Perlin::new(seed), 3),
+            frequency: 6.0,
+        },
+        Translate::new(Sphere::new(0.4), Vec3::new(0.5, 0.5, 0.5)),
+    )
+}
+
+#[test]
+fn balanced_meshes_are_manifold() {
+    for seed in 0..8 {
+        for max_depth in 4..7 {
+            let mut marching_cubes = LinearHashedMarchingCubes::new(max_depth);
+            marching_cubes.set_balanced(true);
+            let mut vertices = vec![];
+            let mut indices = vec![];
+            marching_cubes.extract(&clipped_noise(seed), &mut vertices, &mut indices);
+            assert!(!indices.is_empty());
+            assert_eq!(
+                non_manifold_edges(&indices),
+                0,
+                "seed {}, depth {}",
+                seed,
+                max_depth
+            );
+        }
+    }
+}
+
+#[test]
+fn unbalanced_meshes_are_closed() {
+    for seed in 0..8 {
+        let mut marching_cubes = LinearHashedMarchingCubes::new(6);
+        let mut vertices = vec![];
+        let mut indices = vec![];
+        marching_cubes.extract(&clipped_noise(seed), &mut vertices, &mut indices);
+        assert_eq!(open_edges(&[(vertices, indices)]), 0, "seed {}", seed);
+    }
+}
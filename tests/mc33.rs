@@ -0,0 +1,36 @@
+// Copyright 2018 Tristam MacDonald
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+extern crate isosurface;
+
+mod common;
+
+use common::{non_manifold_edges, open_edges, Scattered, SCATTERED_SAMPLES};
+use isosurface::marching_cubes::{Classification, MarchingCubes};
+use isosurface::math::Vec3;
+use isosurface::region::Region;
+
+#[test]
+fn manifold_and_closed_on_scattered_values() {
+    let region = Region::new(Vec3::zero(), Vec3::one(), [SCATTERED_SAMPLES; 3]);
+    for seed in 0..8 {
+        let mut marching_cubes = MarchingCubes::with_region(region);
+        marching_cubes.set_classification(Classification::Mc33);
+        let mut vertices = vec![];
+        let mut indices = vec![];
+        marching_cubes.extract(&Scattered { seed }, &mut vertices, &mut indices);
+        assert!(!indices.is_empty());
+        assert_eq!(non_manifold_edges(&indices), 0, "seed {}", seed);
+        assert_eq!(open_edges(&[(vertices, indices)]), 0, "seed {}", seed);
+    }
+}
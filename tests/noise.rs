@@ -0,0 +1,44 @@
+// Copyright 2018 Tristam MacDonald
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+extern crate isosurface;
+
+use isosurface::math::Vec3;
+use isosurface::noise::{Fbm, Heightfield, Noise, Perlin};
+use isosurface::source::{HermiteSource, Source};
+
+#[test]
+fn heightfield_normal_is_the_gradient_of_its_distance_on_the_ground() {
+    let terrain = Heightfield::new(Fbm::new(Perlin::new(3), 4), 0.8);
+    let e = 0.001;
+    for i in 0..100 {
+        let x = (i as f32 * 0.173).sin() * 3.0;
+        let z = (i as f32 * 0.311).cos() * 3.0;
+        let y = terrain.noise.sample_with_gradient(x, 0.0, z).0 * terrain.scale;
+
+        let estimate = Vec3::new(
+            terrain.sample(x + e, y, z) - terrain.sample(x - e, y, z),
+            terrain.sample(x, y + e, z) - terrain.sample(x, y - e, z),
+            terrain.sample(x, y, z + e) - terrain.sample(x, y, z - e),
+        ) * (0.5 / e);
+        let normal = terrain.sample_normal(x, y, z);
+        let error = (normal - estimate).length();
+        assert!(error < 0.05, "{} at ({}, {}, {})", error, x, y, z);
+
+        // Above and below the ground, the normal is that of the ground
+        for &offset in &[-0.6, 0.4] {
+            let error = (terrain.sample_normal(x, y + offset, z) - normal).length();
+            assert!(error < 1e-6, "{} at ({}, {}, {})", error, x, y + offset, z);
+        }
+    }
+}
@@ -0,0 +1,67 @@
+// Copyright 2018 Tristam MacDonald
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "rayon")]
+
+extern crate isosurface;
+
+use isosurface::marching_cubes::{Classification, MarchingCubes};
+use isosurface::math::Vec3;
+use isosurface::noise::{Fbm, Perlin};
+use isosurface::region::Region;
+use isosurface::sdf::{Sphere, Torus, Union};
+use isosurface::source::HermiteSource;
+use isosurface::transform::Translate;
+
+fn assert_identical<S: HermiteSource + Sync>(source: &S, marching_cubes: &mut MarchingCubes) {
+    let mut vertices = vec![];
+    let mut indices = vec![];
+    marching_cubes.extract_with_normals(source, &mut vertices, &mut indices);
+
+    let mut par_vertices = vec![];
+    let mut par_indices = vec![];
+    marching_cubes.par_extract_with_normals(source, &mut par_vertices, &mut par_indices);
+
+    assert!(!indices.is_empty());
+    assert_eq!(vertices, par_vertices);
+    assert_eq!(indices, par_indices);
+}
+
+#[test]
+fn matches_serial_extraction() {
+    let source = Union::new(
+        Translate::new(Sphere::new(0.3), Vec3::new(0.4, 0.5, 0.45)),
+        Translate::new(Torus::new(0.25, 0.08), Vec3::new(0.6, 0.5, 0.55)),
+    );
+    // Sizes which don't divide evenly into slabs, as well as those which do
+    for &size in &[3, 17, 32, 61] {
+        assert_identical(&source, &mut MarchingCubes::new(size));
+    }
+
+    let region = Region::new(Vec3::zero(), Vec3::new(1.0, 0.7, 1.3), [23, 19, 47]);
+    let mut marching_cubes = MarchingCubes::with_region(region);
+    marching_cubes.set_classification(Classification::Mc33);
+    assert_identical(&source, &mut marching_cubes);
+}
+
+#[test]
+fn matches_serial_extraction_of_noise() {
+    let mut noise = Fbm::new(Perlin::new(7), 4);
+    noise.frequency = 4.0;
+    for &iso_level in &[-0.2, 0.0, 0.3] {
+        let mut marching_cubes = MarchingCubes::new(40);
+        marching_cubes.set_iso_level(iso_level);
+        assert_identical(&noise, &mut marching_cubes);
+    }
+}
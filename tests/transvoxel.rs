@@ -0,0 +1,174 @@
+// Copyright 2018 Tristam MacDonald
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate isosurface;
+
+mod common;
+
+use common::open_edges;
+use isosurface::marching_cubes::{Faces, MarchingCubes};
+use isosurface::math::Vec3;
+use isosurface::region::Region;
+use isosurface::sdf::{Sphere, Torus, Union};
+use isosurface::source::Source;
+use isosurface::transform::Translate;
+
+const SAMPLES: usize = 17;
+
+fn chunk<S: Source>(source: &S, origin: Vec3, size: f32, faces: Faces) -> (Vec<f32>, Vec<u32>) {
+    let region = Region::new(origin, Vec3::one() * size, [SAMPLES; 3]);
+    let mut vertices = vec![];
+    let mut indices = vec![];
+    MarchingCubes::with_region(region).extract_with_transitions(
+        source,
+        faces,
+        &mut vertices,
+        &mut indices,
+    );
+    (vertices, indices)
+}
+
+#[test]
+fn corner_of_two_transition_faces() {
+    // A fine chunk in the corner between three coarse chunks, with the surface crossing both
+    // transition faces and the edge where they meet
+    let source = Translate::new(Sphere::new(0.35), Vec3::new(2.0, 2.0, 0.45));
+    let meshes = [
+        chunk(
+            &source,
+            Vec3::new(1.0, 1.0, 0.0),
+            1.0,
+            Faces::positive(0) | Faces::positive(1),
+        ),
+        chunk(&source, Vec3::new(2.0, 0.0, 0.0), 2.0, Faces::empty()),
+        chunk(&source, Vec3::new(0.0, 2.0, 0.0), 2.0, Faces::empty()),
+        chunk(&source, Vec3::new(2.0, 2.0, 0.0), 2.0, Faces::empty()),
+    ];
+    assert_eq!(open_edges(&meshes), 0);
+}
+
+#[test]
+fn transition_face_beside_same_resolution_chunk() {
+    // A coarse chunk borders the fine chunks along one face, and a fine chunk along one edge, so
+    // the fine chunks disagree about which of their faces have transition cells
+    let source = Union::new(
+        Translate::new(Sphere::new(0.6), Vec3::new(2.0, 2.1, 0.9)),
+        Translate::new(Torus::new(0.5, 0.12), Vec3::new(1.9, 2.0, 1.0)),
+    );
+    let mut meshes = vec![chunk(
+        &source,
+        Vec3::new(2.0, 0.0, 0.0),
+        2.0,
+        Faces::empty(),
+    )];
+    for z in 0..2 {
+        for y in 0..4 {
+            for x in 0..4 {
+                if x >= 2 && y < 2 {
+                    continue;
+                }
+                let mut faces = Faces::empty();
+                if x == 1 && y < 2 {
+                    faces = faces | Faces::positive(0);
+                }
+                if x >= 2 && y == 2 {
+                    faces = faces | Faces::negative(1);
+                }
+                let origin = Vec3::new(x as f32, y as f32, z as f32);
+                meshes.push(chunk(&source, origin, 1.0, faces));
+            }
+        }
+    }
+    assert_eq!(open_edges(&meshes), 0);
+}
+
+// Sets the 9 samples of one transition cell from the bits of a case, with every other sample
+// outside, so the surface is closed. Positions are in units of the fine chunk's cells, with the
+// transition face at u = 4, and the cell spanning v and w from 2 to 4, where u, v and w are the
+// world axes starting from `axis`.
+struct TransitionCase {
+    axis: usize,
+    case: usize,
+}
+
+impl Source for TransitionCase {
+    fn sample(&self, x: f32, y: f32, z: f32) -> f32 {
+        let p = [x, y, z];
+        let u = p[self.axis].round() as usize;
+        let v = p[(self.axis + 1) % 3].round() as usize;
+        let w = p[(self.axis + 2) % 3].round() as usize;
+        if u == 4 && (2..5).contains(&v) && (2..5).contains(&w) {
+            let bit = (w - 2) * 3 + v - 2;
+            if self.case & (1 << bit) != 0 {
+                return -1.0;
+            }
+        }
+        1.0
+    }
+}
+
+#[test]
+fn every_transition_case_closes_against_coarse_neighbour() {
+    // Maps u, v and w to the world axes
+    let world = |axis: usize, u: f32, v: f32, w: f32| {
+        let mut p = [0.0; 3];
+        p[axis] = u;
+        p[(axis + 1) % 3] = v;
+        p[(axis + 2) % 3] = w;
+        Vec3::new(p[0], p[1], p[2])
+    };
+    let samples = |axis: usize, u: usize, vw: usize| {
+        let mut samples = [vw; 3];
+        samples[axis] = u;
+        samples
+    };
+
+    for axis in 0..3 {
+        for &positive in &[true, false] {
+            // The fine chunk lies on the side of the transition face given by its direction
+            let (fine_u, coarse_u, faces) = if positive {
+                (0.0, 4.0, Faces::positive(axis))
+            } else {
+                (4.0, 0.0, Faces::negative(axis))
+            };
+            let size = world(axis, 4.0, 8.0, 8.0);
+            let fine = Region::new(world(axis, fine_u, 0.0, 0.0), size, samples(axis, 5, 9));
+            let coarse = Region::new(world(axis, coarse_u, 0.0, 0.0), size, samples(axis, 3, 5));
+
+            for case in 0..512 {
+                let source = TransitionCase { axis, case };
+                let mut meshes = vec![];
+                for &(region, faces) in &[(fine, faces), (coarse, Faces::empty())] {
+                    let mut vertices = vec![];
+                    let mut indices = vec![];
+                    MarchingCubes::with_region(region).extract_with_transitions(
+                        &source,
+                        faces,
+                        &mut vertices,
+                        &mut indices,
+                    );
+                    meshes.push((vertices, indices));
+                }
+                assert_eq!(
+                    open_edges(&meshes),
+                    0,
+                    "axis {}, positive {}, case {}",
+                    axis,
+                    positive,
+                    case
+                );
+            }
+        }
+    }
+}
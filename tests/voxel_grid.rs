@@ -0,0 +1,33 @@
+// Copyright 2018 Tristam MacDonald
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+extern crate isosurface;
+
+use isosurface::math::Vec3;
+use isosurface::region::Region;
+use isosurface::sdf::Sphere;
+use isosurface::voxel_grid::VoxelGrid;
+
+#[test]
+#[should_panic(expected = "at least 2 samples")]
+fn new_rejects_a_single_sample_along_an_axis() {
+    let region = Region::new(Vec3::zero(), Vec3::one(), [4, 4, 1]);
+    VoxelGrid::new(region, vec![0.5; 16]);
+}
+
+#[test]
+#[should_panic(expected = "at least 2 samples")]
+fn from_source_rejects_a_single_sample_along_an_axis() {
+    let region = Region::new(Vec3::zero(), Vec3::one(), [1, 4, 4]);
+    VoxelGrid::from_source(region, &Sphere::new(0.5));
+}